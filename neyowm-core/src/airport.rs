@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use crate::xplane::autopilot::navigation::{distance, FEET_PER_NM};
+use crate::Waypoint;
+
+/// Land airport from the nav database, placed at the middle of its longest runway.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Airport {
+    pub icao: String,
+    pub name: String,
+    pub position: Waypoint,
+    // Feet MSL
+    pub elevation: f64,
+    // Feet
+    pub runway_length: f64,
+}
+
+impl Airport {
+    /// Land airports in X-Plane's `apt.dat` format, leaving out seaplane bases, heliports and
+    /// any airport without a runway.
+    pub fn parse_apt_dat(source: &str) -> Vec<Self> {
+        let mut airports = Vec::new();
+        let mut current: Option<(String, String, f64)> = None;
+        let mut longest: Option<(Waypoint, f64)> = None;
+
+        // Each airport runs from its header row to the next header of any kind
+        let mut finish = |current: Option<(String, String, f64)>,
+                          longest: Option<(Waypoint, f64)>| {
+            if let (Some((icao, name, elevation)), Some((position, runway_length))) =
+                (current, longest)
+            {
+                airports.push(Airport {
+                    icao,
+                    name,
+                    position,
+                    elevation,
+                    runway_length,
+                });
+            }
+        };
+
+        for line in source.lines() {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            match fields.first() {
+                // Land airport: elevation, two unused fields, identifier and name
+                Some(&"1") if fields.len() >= 5 => {
+                    finish(current.take(), longest.take());
+                    current = fields[1]
+                        .parse::<f64>()
+                        .ok()
+                        .map(|elevation| (fields[4].to_string(), fields[5..].join(" "), elevation));
+                }
+                Some(&"16") | Some(&"17") | Some(&"99") => {
+                    finish(current.take(), longest.take());
+                }
+                // Land runway, with the latitude and longitude of either end
+                Some(&"100") if current.is_some() && fields.len() >= 20 => {
+                    let ends = [(fields[9], fields[10]), (fields[18], fields[19])].map(
+                        |(latitude, longitude)| {
+                            Some(Waypoint {
+                                latitude: latitude.parse().ok()?,
+                                longitude: longitude.parse().ok()?,
+                            })
+                        },
+                    );
+
+                    if let [Some(start), Some(end)] = ends {
+                        let length = runway_length(&start, &end);
+                        if longest
+                            .as_ref()
+                            .is_none_or(|(_, longest)| length > *longest)
+                        {
+                            let midpoint = Waypoint {
+                                latitude: (start.latitude + end.latitude) / 2.0,
+                                longitude: (start.longitude + end.longitude) / 2.0,
+                            };
+                            longest = Some((midpoint, length));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        finish(current, longest);
+        airports
+    }
+
+    /// Airports in an `apt.dat` file. Names are not always UTF-8, so anything else is replaced.
+    pub fn load(path: &Path) -> Result<Vec<Self>, std::io::Error> {
+        let source = std::fs::read(path)?;
+        Ok(Self::parse_apt_dat(&String::from_utf8_lossy(&source)))
+    }
+}
+
+// Feet between two runway ends
+fn runway_length(start: &Waypoint, end: &Waypoint) -> f64 {
+    distance(start, end) * FEET_PER_NM
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_apt_dat() {
+        let source = "\
+I
+1100 Version
+
+1    22 0 0 CYUL Montreal Trudeau
+100 60.96 1 1 0.25 1 3 1 06L 45.45 -73.75 0 0 3 0 1 0 24R 45.47 -73.72 0 0 3 0 1 0
+100 60.96 1 1 0.25 1 3 1 10 45.46 -73.76 0 0 3 0 1 0 28 45.46 -73.75 0 0 3 0 1 0
+17   100 0 0 XHEL Heliport
+102 H1 45.0 -73.0 0 10 10 1 0 0 0.25 0
+1    90 0 0 NORW No runways
+99
+";
+
+        let airports = Airport::parse_apt_dat(source);
+        assert_eq!(airports.len(), 1);
+        assert_eq!(airports[0].icao, "CYUL");
+        assert_eq!(airports[0].name, "Montreal Trudeau");
+        assert_eq!(airports[0].elevation, 22.0);
+        assert!((airports[0].position.latitude - 45.46).abs() < 1e-9);
+        assert!(airports[0].runway_length > 10_000.0);
+    }
+}
@@ -0,0 +1,205 @@
+use serde::Deserialize;
+
+use crate::{ControlTerms, Gains};
+
+/// Turns the measurement on one axis into a control output that drives it toward the
+/// setpoint. The autopilot flies roll and pitch each through one of these, so a different
+/// controller can be dropped in per axis without touching the modes above it.
+pub trait ControlLaw: Send {
+    fn setpoint(&self) -> f64;
+
+    fn set_setpoint(&mut self, setpoint: f64);
+
+    /// Clears whatever has built up between updates, such as an integral, when the
+    /// autopilot engages.
+    fn reset(&mut self);
+
+    /// Output for a measurement taken dt seconds after the previous one, or None for the
+    /// first measurement.
+    fn update(&mut self, measurement: f64, dt: Option<f64>) -> f64;
+
+    /// Terms of the last update, for laws made up of proportional, integral and derivative
+    /// terms.
+    fn terms(&self) -> Option<ControlTerms> {
+        None
+    }
+
+    fn gains(&self) -> Option<Gains> {
+        None
+    }
+
+    /// Retunes the law while it runs. Returns whether the gains were set, which they are not
+    /// for a law without gains or for gains that are negative or not finite.
+    fn set_gains(&mut self, _gains: Gains) -> bool {
+        false
+    }
+}
+
+/// Control law for one axis as it would be written in configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "law", rename_all = "snake_case")]
+pub enum ControlLawConfig {
+    // Gains per unit of error and per second; the limit holds both the integral and the output
+    Pid {
+        kp: f64,
+        ki: f64,
+        kd: f64,
+        limit: f64,
+    },
+}
+
+impl ControlLawConfig {
+    pub fn build(&self) -> Box<dyn ControlLaw> {
+        match *self {
+            ControlLawConfig::Pid { kp, ki, kd, limit } => Box::new(Pid::new(kp, ki, kd, limit)),
+        }
+    }
+}
+
+impl Default for ControlLawConfig {
+    // Attitude loop tuned for a light single-engine aircraft, per degree of error. The
+    // integral gain matches what was tuned per sample at a telemetry period of about a
+    // quarter second.
+    fn default() -> Self {
+        ControlLawConfig::Pid {
+            kp: 0.1,
+            ki: 0.04,
+            kd: 0.0,
+            limit: 15.0,
+        }
+    }
+}
+
+/// PID controller with gains per second of elapsed time rather than per sample. The
+/// derivative acts on the measurement so setpoint changes do not kick the output, and the
+/// integral and the output are each held within the limit.
+#[derive(Debug, Clone)]
+pub struct Pid {
+    pub kp: f64,
+    pub ki: f64,
+    pub kd: f64,
+    pub limit: f64,
+    setpoint: f64,
+    integral: f64,
+    last_measurement: Option<f64>,
+    terms: Option<ControlTerms>,
+}
+
+impl Pid {
+    pub fn new(kp: f64, ki: f64, kd: f64, limit: f64) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            limit,
+            setpoint: 0.0,
+            integral: 0.0,
+            last_measurement: None,
+            terms: None,
+        }
+    }
+}
+
+impl ControlLaw for Pid {
+    fn setpoint(&self) -> f64 {
+        self.setpoint
+    }
+
+    fn set_setpoint(&mut self, setpoint: f64) {
+        self.setpoint = setpoint;
+    }
+
+    fn reset(&mut self) {
+        self.integral = 0.0;
+    }
+
+    // Without a dt only the proportional and the existing integral terms contribute
+    fn update(&mut self, measurement: f64, dt: Option<f64>) -> f64 {
+        let error = self.setpoint - measurement;
+        let p = self.kp * error;
+
+        let mut d = 0.0;
+        if let Some(dt) = dt {
+            self.integral = (self.integral + self.ki * error * dt).clamp(-self.limit, self.limit);
+
+            if let Some(last_measurement) = self.last_measurement {
+                if dt > 0.0 {
+                    d = -self.kd * (measurement - last_measurement) / dt;
+                }
+            }
+        }
+
+        self.last_measurement = Some(measurement);
+        self.terms = Some(ControlTerms {
+            p,
+            i: self.integral,
+            d,
+        });
+
+        (p + self.integral + d).clamp(-self.limit, self.limit)
+    }
+
+    fn terms(&self) -> Option<ControlTerms> {
+        self.terms
+    }
+
+    fn gains(&self) -> Option<Gains> {
+        Some(Gains {
+            kp: self.kp,
+            ki: self.ki,
+            kd: self.kd,
+        })
+    }
+
+    fn set_gains(&mut self, gains: Gains) -> bool {
+        if ![gains.kp, gains.ki, gains.kd]
+            .iter()
+            .all(|gain| gain.is_finite() && *gain >= 0.0)
+        {
+            return false;
+        }
+
+        self.kp = gains.kp;
+        self.ki = gains.ki;
+        self.kd = gains.kd;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integral_follows_elapsed_time() {
+        let mut fast = Pid::new(0.0, 0.5, 0.0, 10.0);
+        fast.set_setpoint(1.0);
+        let mut slow = fast.clone();
+
+        for _ in 0..10 {
+            fast.update(0.0, Some(0.1));
+        }
+        let output = slow.update(0.0, Some(1.0));
+
+        // With only an integral gain the output is the integral
+        assert!((fast.update(0.0, Some(0.0)) - output).abs() < 1e-9);
+        assert!((output - 0.5).abs() < 1e-9);
+    }
+    #[test]
+    fn test_rejects_gains_that_are_not_finite() {
+        let mut pid = Pid::new(1.0, 0.5, 0.1, 10.0);
+
+        for kp in [f64::INFINITY, f64::NAN, -1.0] {
+            let gains = Gains {
+                kp,
+                ki: 0.0,
+                kd: 0.0,
+            };
+            assert!(!pid.set_gains(gains));
+        }
+        assert_eq!(pid.gains().unwrap().kp, 1.0);
+
+        pid.set_setpoint(0.0);
+        assert_eq!(pid.update(0.0, Some(0.1)), 0.0);
+    }
+}
@@ -1,7 +1,13 @@
+mod airport;
+mod control_law;
+mod profile;
 mod server;
 mod types;
 pub mod xplane;
 
+pub use airport::Airport;
+pub use control_law::{ControlLaw, ControlLawConfig, Pid};
+pub use profile::{AircraftProfile, ProfileError};
 pub use server::Bridge;
 pub use server::Server;
 pub use types::*;
@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{ControlLawConfig, EnvelopeLimits, GoAroundProfile, VSpeeds};
+
+/// Profiles shipped with the controller, the first being the one flown when nothing else
+/// matches the loaded aircraft.
+const BUILTIN_PROFILES: [&str; 2] = [
+    include_str!("../profiles/c172.toml"),
+    include_str!("../profiles/sr22.toml"),
+];
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Io(std::io::Error),
+    Parse(toml::de::Error),
+}
+
+/// Tuning, limits and reference speeds for one aircraft type, read from TOML.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AircraftProfile {
+    pub name: String,
+    // ICAO type designators the profile is selected for
+    #[serde(default)]
+    pub icao: Vec<String>,
+    #[serde(default)]
+    pub roll: ControlLawConfig,
+    #[serde(default)]
+    pub pitch: ControlLawConfig,
+    #[serde(default)]
+    pub limits: EnvelopeLimits,
+    pub speeds: VSpeeds,
+    // Distance covered per unit of height lost at the best glide speed, engine out
+    pub glide_ratio: f64,
+    #[serde(default)]
+    pub go_around: GoAroundProfile,
+}
+
+impl AircraftProfile {
+    pub fn parse(source: &str) -> Result<Self, ProfileError> {
+        toml::from_str(source).map_err(ProfileError::Parse)
+    }
+
+    pub fn builtin() -> Vec<Self> {
+        BUILTIN_PROFILES
+            .iter()
+            .map(|source| Self::parse(source).expect("built-in profiles are valid"))
+            .collect()
+    }
+
+    /// Every `.toml` file in a directory, in file name order.
+    pub fn load_dir(dir: &Path) -> Result<Vec<Self>, ProfileError> {
+        let mut paths = std::fs::read_dir(dir)
+            .map_err(ProfileError::Io)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ProfileError::Io)?;
+        paths.retain(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "toml")
+        });
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| Self::parse(&std::fs::read_to_string(path).map_err(ProfileError::Io)?))
+            .collect()
+    }
+
+    /// Whether the profile is for the aircraft with the given ICAO type designator.
+    pub fn matches(&self, icao: &str) -> bool {
+        self.icao
+            .iter()
+            .any(|designator| designator.eq_ignore_ascii_case(icao.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_profiles_parse() {
+        let profiles = AircraftProfile::builtin();
+        assert_eq!(profiles[0].roll, ControlLawConfig::default());
+        assert_eq!(profiles[0].limits, EnvelopeLimits::default());
+        assert!(profiles[1].matches("sr22 "));
+        assert!(!profiles[1].matches("C172"));
+    }
+}
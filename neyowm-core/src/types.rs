@@ -1,7 +1,27 @@
+use serde::Deserialize;
+
 #[derive(Debug, Clone)]
 pub enum ClientBoundMessage {
     Shutdown,
     SetAutopilotMode(AutopilotMode),
+    SetAutothrottleMode(AutothrottleMode),
+    SetFlightDirector(bool),
+    // Automatic recovery from an upset or stall, or a recovery started straight away
+    SetUpsetProtection(bool),
+    StartRecovery,
+    // Attitude the modes command while the flight director is on, None once they stop
+    FlightDirector(Option<FlightDirectorCue>),
+    AutopilotStatus(AutopilotStatus),
+    AutopilotAlert(AutopilotAlert),
+    ControllerInternals(ControllerInternals),
+    SetGains(ControlAxis, Gains),
+    StartAutotune(ControlAxis, TuningRule),
+    StopAutotune,
+    AutotuneStatus(AutotuneStatus),
+    SelectProfile(ProfileSelection),
+    ProfileStatus(ProfileStatus),
+    GlideStatus(GlideStatus),
+    TerrainStatus(TerrainStatus),
     UpdateConnectionStatus(ConnectionStatus),
     UpdateTelemetry(TelemetryUpdate),
 }
@@ -40,6 +60,46 @@ pub enum TelemetryUpdate {
         velocity: (f64, f64, f64),
         wet: bool,
     },
+    // Terrain probed at points ahead along the track (degrees true)
+    TerrainProfile {
+        track: f64,
+        samples: Vec<TerrainSample>,
+    },
+    // ICAO type designator of the loaded aircraft
+    Aircraft {
+        icao: String,
+    },
+    // Seconds the sim has been running, which advances with time acceleration and stops
+    // while paused
+    SimTime {
+        seconds: f64,
+    },
+    // Indicated airspeed in knots
+    Airspeed {
+        indicated: f64,
+    },
+    Ground {
+        on_ground: bool,
+    },
+    // Sideslip angle in degrees, positive with the relative wind from the right
+    Sideslip {
+        beta: f64,
+    },
+    // Normal load factor in g
+    LoadFactor {
+        normal: f64,
+    },
+    StallWarning {
+        warning: bool,
+    },
+    // Needle deflections in dots, positive when the localizer is to the right and the
+    // glideslope is above
+    Nav1 {
+        localizer: f64,
+        glideslope: f64,
+        localizer_valid: bool,
+        glideslope_valid: bool,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -48,8 +108,445 @@ pub enum ConnectionStatus {
     Disconnected,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AutopilotMode {
     Off,
-    Hold { roll: f64, pitch: f64 },
+    Hold {
+        roll: f64,
+        pitch: f64,
+    },
+    // Altitude in feet MSL
+    AltitudeHold {
+        roll: f64,
+        altitude: f64,
+    },
+    // Heading and bank limit in degrees, altitude in feet MSL
+    Heading {
+        heading: f64,
+        bank_limit: f64,
+        altitude: f64,
+    },
+    // Bank limit in degrees, altitude in feet MSL
+    Lnav {
+        waypoints: Vec<Waypoint>,
+        bank_limit: f64,
+        altitude: f64,
+    },
+    // Flies the intercept heading at the given altitude until the localizer and then the
+    // glideslope are captured, then down the localizer course (degrees true). With a flare
+    // height (feet above the terrain) it also lands.
+    Approach {
+        heading: f64,
+        course: f64,
+        bank_limit: f64,
+        altitude: f64,
+        flare_height: Option<f64>,
+    },
+    // Climb rate in feet per minute, wings level. With an altitude (feet MSL) armed, it is
+    // captured and held once reached
+    VerticalSpeed {
+        fpm: f64,
+        altitude: Option<f64>,
+    },
+    // Full throttle down the runway heading, rotating at the given speed (knots) to the given
+    // pitch (degrees), then handing off to a heading hold climbing to the altitude (feet MSL)
+    Takeoff {
+        heading: f64,
+        rotate_speed: f64,
+        rotate_pitch: f64,
+        altitude: f64,
+    },
+    // Circles the center at the radius in nautical miles, bank limit in degrees, altitude in
+    // feet MSL
+    Orbit {
+        center: Waypoint,
+        radius: f64,
+        direction: TurnDirection,
+        bank_limit: f64,
+        altitude: f64,
+    },
+    // Racetrack hold at the fix on the inbound course (degrees true), entered the standard way
+    // from wherever the aircraft is. Bank limit in degrees, altitude in feet MSL
+    Holding {
+        fix: Waypoint,
+        inbound_course: f64,
+        leg: HoldLeg,
+        direction: TurnDirection,
+        bank_limit: f64,
+        altitude: f64,
+    },
+    // Engine out: pitches for the best glide speed and steers for the nearest airport in
+    // reach, banking no more than the limit in degrees
+    Glide {
+        bank_limit: f64,
+    },
+    // Full power and the profile's go-around pitch on the heading (degrees, or the one the
+    // aircraft is on when None), retracting the flaps in stages, then handing off to a
+    // heading hold climbing to the altitude (feet MSL)
+    GoAround {
+        heading: Option<f64>,
+        altitude: f64,
+    },
+    // Holds the heading (degrees) with the bank limit (degrees), climbing and descending to
+    // stay the clearance (feet) above the terrain below and ahead
+    TerrainFollow {
+        heading: f64,
+        bank_limit: f64,
+        clearance: f64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnDirection {
+    Left,
+    Right,
+}
+
+/// Length of the legs of a hold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HoldLeg {
+    Time { minutes: f64 },
+    Distance { nm: f64 },
+}
+
+/// What the autopilot is actually flying, channel by channel, for the flight mode annunciator.
+/// A channel is None when nothing is engaged on it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AutopilotStatus {
+    pub mode: Option<AutopilotMode>,
+    pub thrust: Option<ThrustMode>,
+    pub vertical: Option<VerticalMode>,
+    pub vertical_armed: Option<VerticalMode>,
+    pub lateral: Option<LateralMode>,
+    pub lateral_armed: Option<LateralMode>,
+    pub protection: EnvelopeProtection,
+    // The modes are shown as cues to fly by hand rather than flown
+    pub flight_director: bool,
+    pub upset_protection: bool,
+    // Recovering from an upset, in place of whatever mode is engaged
+    pub recovery: Option<RecoveryStatus>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryStatus {
+    pub upset: Upset,
+    pub phase: RecoveryPhase,
+}
+
+/// What a recovery is recovering from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upset {
+    Stall,
+    NoseHigh,
+    NoseLow,
+    // Steep bank with the nose near the horizon
+    Bank,
+    // Asked for from an attitude that is not an upset, so just levelled out
+    Requested,
+}
+
+/// Stages of the standard recovery, flown in order: the wing is unloaded, the wings rolled
+/// level, then the nose brought back to the horizon while the power is managed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryPhase {
+    Unload,
+    RollLevel,
+    Pitch,
+}
+
+/// Roll and pitch, in degrees, the engaged modes would fly the aircraft at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlightDirectorCue {
+    pub roll: f64,
+    pub pitch: f64,
+}
+
+/// Something the autopilot disengaged for, to be brought to the pilot's attention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutopilotAlert {
+    // The pilot moved the flight controls against the autopilot
+    PilotOverride,
+    // The pilot moved the throttle against the autothrottle
+    ThrottleOverride,
+    // The sim stopped answering
+    ConnectionLost,
+    // Disengaged for want of an input the engaged modes cannot fly without
+    TelemetryLost(TelemetryInput),
+    // Reverted to wings level at the current pitch for want of an input the mode needs
+    Degraded(TelemetryInput),
+    // Took over from whatever was flying to recover from an upset
+    Upset(Upset),
+    // Went around from an approach that did not stay stable
+    UnstableApproach,
+}
+
+/// Telemetry the autopilot watches the age of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryInput {
+    Attitude,
+    Position,
+    Airspeed,
+    Nav1,
+    // Terrain below, and the terrain probed ahead while following it
+    Terrain,
+    TerrainAhead,
+}
+
+/// Which envelope limits are currently overriding the active mode's targets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnvelopeProtection {
+    pub bank: bool,
+    pub pitch: bool,
+    pub load_factor: bool,
+}
+
+/// Per-aircraft limits the autopilot never exceeds, whatever the mode asks for.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct EnvelopeLimits {
+    // Degrees
+    pub max_bank: f64,
+    pub min_pitch: f64,
+    pub max_pitch: f64,
+    // Normal load factor in g
+    pub min_load_factor: f64,
+    pub max_load_factor: f64,
+    // Fraction of full travel, and full travel per second
+    pub max_deflection: f64,
+    pub max_deflection_rate: f64,
+}
+
+impl Default for EnvelopeLimits {
+    // Conservative limits for a light single-engine aircraft
+    fn default() -> Self {
+        Self {
+            max_bank: 35.0,
+            min_pitch: -10.0,
+            max_pitch: 15.0,
+            min_load_factor: 0.3,
+            max_load_factor: 1.8,
+            max_deflection: 1.0,
+            max_deflection_rate: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlAxis {
+    Roll,
+    Pitch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gains {
+    pub kp: f64,
+    pub ki: f64,
+    pub kd: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ControlTerms {
+    pub p: f64,
+    pub i: f64,
+    pub d: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TuningRule {
+    ZieglerNichols,
+    TyreusLuyben,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutotuneFailure {
+    // The autopilot has to be flying the axis to run the experiment on it
+    NotEngaged,
+    Cancelled,
+    Diverged,
+    TimedOut,
+    NoOscillation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AutotuneStatus {
+    Running {
+        axis: ControlAxis,
+        cycles: usize,
+    },
+    // Ultimate gain per degree and ultimate period in seconds, with the gains the rule gives
+    Proposed {
+        axis: ControlAxis,
+        ultimate_gain: f64,
+        ultimate_period: f64,
+        gains: Gains,
+    },
+    Failed {
+        axis: ControlAxis,
+        failure: AutotuneFailure,
+    },
+}
+
+/// One update of the control law on an axis, in degrees and fractions of control travel.
+/// Terms and gains are only there for laws made up of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ControllerInternals {
+    pub axis: ControlAxis,
+    pub setpoint: f64,
+    pub measurement: f64,
+    pub output: f64,
+    pub terms: Option<ControlTerms>,
+    pub gains: Option<Gains>,
+}
+
+/// Reference speeds of an aircraft, in knots indicated.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct VSpeeds {
+    pub rotate: f64,
+    pub climb: f64,
+    pub cruise: f64,
+    pub approach: f64,
+    // Best glide, engine out
+    pub glide: f64,
+}
+
+/// How an aircraft goes around: the pitch (degrees) it climbs away at, and the flap settings
+/// it retracts to in turn.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct GoAroundProfile {
+    pub pitch: f64,
+    pub flaps: Vec<FlapStage>,
+}
+
+impl Default for GoAroundProfile {
+    // Half flap straight away and the rest once flying, for a light single
+    fn default() -> Self {
+        Self {
+            pitch: 7.0,
+            flaps: vec![
+                FlapStage {
+                    setting: 0.5,
+                    speed: 0.0,
+                },
+                FlapStage {
+                    setting: 0.0,
+                    speed: 65.0,
+                },
+            ],
+        }
+    }
+}
+
+/// Flap setting (fraction of full extension) retracted to once the speed (knots indicated) is
+/// reached.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct FlapStage {
+    pub setting: f64,
+    pub speed: f64,
+}
+
+/// How far the aircraft can glide, and how it stands against the airport it is gliding for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlideStatus {
+    // Still-air distance in nautical miles down to the terrain below, once terrain is known
+    pub range: Option<f64>,
+    pub airport: Option<GlideTarget>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlideTarget {
+    pub icao: String,
+    // Nautical miles to the airport, and left in hand on reaching it at field elevation;
+    // negative when it is out of reach
+    pub distance: f64,
+    pub margin: f64,
+}
+
+/// Terrain under a point on the track ahead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerrainSample {
+    // Nautical miles ahead, and meters MSL as with the terrain under the aircraft
+    pub distance: f64,
+    pub elevation: f64,
+}
+
+/// How high the aircraft is over the terrain, below and ahead, while following it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerrainStatus {
+    // Feet above the terrain below
+    pub height: f64,
+    // The least clearance along the track ahead, once terrain ahead is known
+    pub ahead: Option<TerrainClearance>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerrainClearance {
+    // Nautical miles ahead, and feet above the terrain there at the current altitude
+    pub distance: f64,
+    pub clearance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProfileSelection {
+    // Follow the loaded aircraft
+    Automatic,
+    Named(String),
+}
+
+/// Aircraft profile the autopilot is flying with, and the ones it could switch to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileStatus {
+    pub active: String,
+    pub available: Vec<String>,
+    pub automatic: bool,
+    pub speeds: VSpeeds,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrustMode {
+    Speed,
+    Takeoff,
+    Retard,
+    GoAround,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalMode {
+    Pitch,
+    VerticalSpeed,
+    AltitudeCapture,
+    AltitudeHold,
+    Glideslope,
+    Flare,
+    Takeoff,
+    Glide,
+    GoAround,
+    TerrainFollow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LateralMode {
+    Roll,
+    Heading,
+    Lnav,
+    Localizer,
+    Runway,
+    Rollout,
+    Orbit,
+    Hold,
+    // Straight for an airport
+    Direct,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AutothrottleMode {
+    Off,
+    // Indicated airspeed in knots
+    Speed { knots: f64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waypoint {
+    pub latitude: f64,
+    pub longitude: f64,
 }
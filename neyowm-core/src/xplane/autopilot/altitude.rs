@@ -0,0 +1,30 @@
+/// Climb rate (feet per minute) commanded per foot of altitude error.
+pub const ALTITUDE_GAIN: f64 = 4.0;
+
+/// Largest climb or descent rate (feet per minute) the altitude hold will command.
+pub const MAX_CLIMB_RATE: f64 = 1000.0;
+
+pub const FEET_PER_METER: f64 = 3.28084;
+
+/// Outer altitude loop: turns altitude error into a climb rate target for the vertical speed
+/// hold, so captures are flown at a bounded rate rather than a fixed pitch.
+pub fn climb_rate_target(target: f64, altitude: f64) -> f64 {
+    limited_climb_rate_target(target, altitude, MAX_CLIMB_RATE)
+}
+
+/// As `climb_rate_target`, with a different limit (feet per minute) on the rate.
+pub fn limited_climb_rate_target(target: f64, altitude: f64, limit: f64) -> f64 {
+    (ALTITUDE_GAIN * (target - altitude)).clamp(-limit, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_climb_rate_target_is_limited() {
+        assert_eq!(climb_rate_target(10_000.0, 2_000.0), MAX_CLIMB_RATE);
+        assert_eq!(climb_rate_target(2_000.0, 10_000.0), -MAX_CLIMB_RATE);
+        assert_eq!(climb_rate_target(10_000.0, 9_950.0), 200.0);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::VerticalMode;
+
+use super::altitude::{self, ALTITUDE_GAIN, MAX_CLIMB_RATE};
+
+/// Altitude error (feet) inside which a capture is complete and the altitude is held.
+const ALTITUDE_HOLD_BAND: f64 = 20.0;
+
+/// Vertical channel for modes with a target altitude: a climb or descent at a set rate with the
+/// altitude armed, then a capture once the rate the altitude loop asks for drops below that
+/// rate, then the hold.
+pub struct AltitudeCapture {
+    target: f64,
+    rate: Option<f64>,
+    mode: VerticalMode,
+}
+
+impl AltitudeCapture {
+    /// Target altitude is in feet. Rate is the climb rate (feet per minute) to fly until the
+    /// capture, or None to climb or descend toward the target at the altitude loop's limit.
+    pub fn new(target: f64, rate: Option<f64>) -> Self {
+        Self {
+            target,
+            rate,
+            mode: VerticalMode::VerticalSpeed,
+        }
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    pub fn mode(&self) -> VerticalMode {
+        self.mode
+    }
+
+    pub fn armed(&self) -> Option<VerticalMode> {
+        (self.mode == VerticalMode::VerticalSpeed).then_some(VerticalMode::AltitudeHold)
+    }
+
+    /// Climb rate (feet per minute) to fly from the given altitude (feet).
+    pub fn climb_rate(&mut self, altitude: f64) -> f64 {
+        let error = self.target - altitude;
+        let rate = self.rate.unwrap_or_else(|| MAX_CLIMB_RATE.copysign(error));
+
+        if self.mode == VerticalMode::VerticalSpeed
+            && rate * error >= 0.0
+            && ALTITUDE_GAIN * error.abs() <= rate.abs()
+        {
+            self.mode = VerticalMode::AltitudeCapture;
+        }
+
+        if self.mode == VerticalMode::AltitudeCapture && error.abs() < ALTITUDE_HOLD_BAND {
+            self.mode = VerticalMode::AltitudeHold;
+        }
+
+        match self.mode {
+            VerticalMode::AltitudeCapture => {
+                altitude::limited_climb_rate_target(self.target, altitude, rate.abs())
+            }
+            VerticalMode::AltitudeHold => altitude::climb_rate_target(self.target, altitude),
+            _ => rate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captures_from_vertical_speed() {
+        let mut capture = AltitudeCapture::new(5_000.0, Some(500.0));
+        assert_eq!(capture.climb_rate(4_000.0), 500.0);
+        assert_eq!(capture.mode(), VerticalMode::VerticalSpeed);
+        assert_eq!(capture.armed(), Some(VerticalMode::AltitudeHold));
+
+        assert_eq!(capture.climb_rate(4_900.0), 400.0);
+        assert_eq!(capture.mode(), VerticalMode::AltitudeCapture);
+        assert_eq!(capture.armed(), None);
+
+        capture.climb_rate(4_990.0);
+        assert_eq!(capture.mode(), VerticalMode::AltitudeHold);
+    }
+
+    #[test]
+    fn test_never_captures_climbing_away() {
+        let mut capture = AltitudeCapture::new(3_000.0, Some(500.0));
+        assert_eq!(capture.climb_rate(3_050.0), 500.0);
+        assert_eq!(capture.mode(), VerticalMode::VerticalSpeed);
+    }
+}
@@ -0,0 +1,244 @@
+use super::{
+    heading::heading_error,
+    landing::{Landing, LandingPhase},
+};
+
+/// Localizer deflection (dots) inside which the localizer is captured.
+const LOCALIZER_CAPTURE: f64 = 2.0;
+
+/// Glideslope deflection (dots) inside which the glideslope is captured.
+const GLIDESLOPE_CAPTURE: f64 = 0.3;
+
+/// Track correction (degrees) commanded per dot of localizer deflection.
+const LOCALIZER_GAIN: f64 = 10.0;
+
+/// Steepest angle (degrees) at which the localizer is re-intercepted once captured.
+const MAX_LOCALIZER_CORRECTION: f64 = 30.0;
+
+/// Climb rate correction (feet per minute) commanded per dot of glideslope deflection.
+const GLIDESLOPE_GAIN: f64 = 300.0;
+
+const GLIDESLOPE_ANGLE: f64 = 3.0;
+
+const FEET_PER_MINUTE_PER_KNOT: f64 = 101.269;
+
+/// Height (feet above the terrain) below which the approach has to be stable to go on.
+const STABLE_HEIGHT: f64 = 1000.0;
+
+/// Largest localizer and glideslope deflection (dots) of a stable approach.
+const STABLE_DEFLECTION: f64 = 1.0;
+
+/// Knots below and above the approach speed a stable approach stays within.
+const STABLE_SLOW: f64 = 5.0;
+const STABLE_FAST: f64 = 20.0;
+
+/// Fastest descent (feet per minute) of a stable approach.
+const STABLE_SINK_RATE: f64 = 1000.0;
+
+/// How long (seconds) the approach has to stay unstable before it is given up, so a gust
+/// does not end it.
+const UNSTABLE_TIME: f64 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureState {
+    Armed,
+    Captured,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApproachStatus {
+    pub localizer: CaptureState,
+    pub glideslope: CaptureState,
+    pub landing: Option<LandingPhase>,
+}
+
+/// NAV1 receiver state, see `TelemetryUpdate::Nav1`.
+#[derive(Debug, Clone, Copy)]
+pub struct Nav1 {
+    pub localizer: f64,
+    pub glideslope: f64,
+    pub localizer_valid: bool,
+    pub glideslope_valid: bool,
+}
+
+/// Arms the localizer and glideslope on engagement and captures each as its needle comes
+/// in. The glideslope only captures once established on the localizer, and the landing only
+/// once established on the glideslope.
+pub struct Approach {
+    course: f64,
+    status: ApproachStatus,
+    landing: Option<Landing>,
+    approach_speed: f64,
+    unstable_for: f64,
+}
+
+impl Approach {
+    /// Course is the localizer's, in degrees true. Flare height is in feet above the terrain,
+    /// or None to leave the landing to the pilot. The approach is to be flown at the approach
+    /// speed (knots).
+    pub fn new(course: f64, flare_height: Option<f64>, approach_speed: f64) -> Self {
+        let landing = flare_height.map(Landing::new);
+        Self {
+            course,
+            status: ApproachStatus {
+                localizer: CaptureState::Armed,
+                glideslope: CaptureState::Armed,
+                landing: landing.as_ref().map(|landing| landing.phase()),
+            },
+            landing,
+            approach_speed,
+            unstable_for: 0.0,
+        }
+    }
+
+    pub fn course(&self) -> f64 {
+        self.course
+    }
+
+    pub fn status(&self) -> &ApproachStatus {
+        &self.status
+    }
+
+    pub fn update(&mut self, nav: &Nav1, heading: f64) {
+        if self.status.localizer == CaptureState::Armed
+            && nav.localizer_valid
+            && nav.localizer.abs() < LOCALIZER_CAPTURE
+            && heading_error(self.course, heading).abs() < 90.0
+        {
+            self.status.localizer = CaptureState::Captured;
+        }
+
+        if self.status.localizer == CaptureState::Captured
+            && self.status.glideslope == CaptureState::Armed
+            && nav.glideslope_valid
+            && nav.glideslope.abs() < GLIDESLOPE_CAPTURE
+        {
+            self.status.glideslope = CaptureState::Captured;
+        }
+    }
+
+    /// Height is in feet above the terrain and throttle the current lever position.
+    pub fn update_landing(&mut self, height: f64, on_ground: bool, throttle: f64) {
+        if self.status.glideslope != CaptureState::Captured {
+            return;
+        }
+
+        if let Some(landing) = &mut self.landing {
+            landing.update(height, on_ground, throttle);
+            self.status.landing = Some(landing.phase());
+        }
+    }
+
+    /// Whether the approach has been unstable for long enough that it should be given up.
+    /// It has to be stable below the stable height, from the glideslope capture until the
+    /// flare: on the needles, at the approach speed (knots) and not descending too fast (feet
+    /// per minute). Height is in feet above the terrain. Once on the ground, with or without
+    /// the landing armed, it is never given up.
+    pub fn is_unstable(
+        &mut self,
+        nav: &Nav1,
+        height: f64,
+        on_ground: bool,
+        airspeed: Option<f64>,
+        climb_rate: f64,
+        dt: f64,
+    ) -> bool {
+        let gated = self.status.glideslope == CaptureState::Captured
+            && height < STABLE_HEIGHT
+            && !on_ground
+            && matches!(self.status.landing, None | Some(LandingPhase::Armed));
+        let unstable = !nav.localizer_valid
+            || !nav.glideslope_valid
+            || nav.localizer.abs() > STABLE_DEFLECTION
+            || nav.glideslope.abs() > STABLE_DEFLECTION
+            || airspeed.is_some_and(|airspeed| {
+                airspeed < self.approach_speed - STABLE_SLOW
+                    || airspeed > self.approach_speed + STABLE_FAST
+            })
+            || climb_rate < -STABLE_SINK_RATE;
+
+        self.unstable_for = if gated && unstable {
+            self.unstable_for + dt
+        } else {
+            0.0
+        };
+        self.unstable_for >= UNSTABLE_TIME
+    }
+
+    pub fn landing_mut(&mut self) -> Option<&mut Landing> {
+        self.landing.as_mut()
+    }
+
+    /// Track to fly down the localizer, once it has been captured.
+    pub fn track(&self, nav: &Nav1) -> Option<f64> {
+        if self.status.localizer != CaptureState::Captured || !nav.localizer_valid {
+            return None;
+        }
+
+        let correction = (LOCALIZER_GAIN * nav.localizer)
+            .clamp(-MAX_LOCALIZER_CORRECTION, MAX_LOCALIZER_CORRECTION);
+        Some((self.course + correction).rem_euclid(360.0))
+    }
+
+    /// Climb rate (feet per minute) to fly down the glideslope at the given speed (knots),
+    /// once it has been captured.
+    pub fn climb_rate(&self, nav: &Nav1, speed: f64) -> Option<f64> {
+        if self.status.glideslope != CaptureState::Captured || !nav.glideslope_valid {
+            return None;
+        }
+
+        let nominal = -speed * FEET_PER_MINUTE_PER_KNOT * GLIDESLOPE_ANGLE.to_radians().tan();
+        Some(nominal + GLIDESLOPE_GAIN * nav.glideslope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glideslope_captures_after_localizer() {
+        let mut approach = Approach::new(90.0, None, 65.0);
+        let mut nav = Nav1 {
+            localizer: 2.5,
+            glideslope: 0.0,
+            localizer_valid: true,
+            glideslope_valid: true,
+        };
+
+        approach.update(&nav, 45.0);
+        assert_eq!(approach.status().localizer, CaptureState::Armed);
+        assert_eq!(approach.status().glideslope, CaptureState::Armed);
+        assert!(approach.track(&nav).is_none());
+
+        nav.localizer = 1.0;
+        approach.update(&nav, 45.0);
+        assert_eq!(approach.status().localizer, CaptureState::Captured);
+        assert_eq!(approach.status().glideslope, CaptureState::Captured);
+        assert_eq!(approach.track(&nav), Some(100.0));
+        assert!(approach.climb_rate(&nav, 90.0).unwrap() < 0.0);
+    }
+
+    #[test]
+    fn test_not_unstable_on_the_ground() {
+        let mut approach = Approach::new(90.0, None, 65.0);
+        let mut nav = Nav1 {
+            localizer: 0.0,
+            glideslope: 0.0,
+            localizer_valid: true,
+            glideslope_valid: true,
+        };
+        approach.update(&nav, 90.0);
+
+        // Glideslope lost on the runway, slowing down
+        nav.glideslope_valid = false;
+        for _ in 0..50 {
+            assert!(!approach.is_unstable(&nav, 0.0, true, Some(40.0), 0.0, 0.1));
+        }
+
+        // The same in the air is given up after a few seconds
+        let unstable =
+            (0..50).any(|_| approach.is_unstable(&nav, 200.0, false, Some(40.0), 0.0, 0.1));
+        assert!(unstable);
+    }
+}
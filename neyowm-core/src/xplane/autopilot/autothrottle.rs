@@ -0,0 +1,58 @@
+/// Throttle (fraction of full travel) commanded per knot of airspeed error.
+const SPEED_GAIN: f64 = 0.05;
+
+/// Rate (throttle per knot-second) at which the trim soaks up a steady speed error.
+const TRIM_GAIN: f64 = 0.005;
+
+/// Fastest the throttle lever may move, in full travel per second.
+const MAX_THROTTLE_RATE: f64 = 0.1;
+
+pub const MIN_THROTTLE: f64 = 0.0;
+pub const MAX_THROTTLE: f64 = 1.0;
+
+/// Holds indicated airspeed by driving the throttle, independent of the lateral and vertical
+/// modes.
+pub struct Autothrottle {
+    trim: f64,
+    throttle: f64,
+}
+
+impl Autothrottle {
+    /// Starts from the current throttle position so engaging doesn't move the lever.
+    pub fn new(throttle: f64) -> Self {
+        let throttle = throttle.clamp(MIN_THROTTLE, MAX_THROTTLE);
+        Self {
+            trim: throttle,
+            throttle,
+        }
+    }
+
+    /// Speeds are in knots and dt in seconds.
+    pub fn throttle(&mut self, target: f64, airspeed: f64, dt: f64) -> f64 {
+        let error = target - airspeed;
+        self.trim = (self.trim + TRIM_GAIN * error * dt).clamp(MIN_THROTTLE, MAX_THROTTLE);
+
+        let desired = (self.trim + SPEED_GAIN * error).clamp(MIN_THROTTLE, MAX_THROTTLE);
+        let max_step = MAX_THROTTLE_RATE * dt;
+        self.throttle += (desired - self.throttle).clamp(-max_step, max_step);
+        self.throttle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_is_rate_limited() {
+        let mut autothrottle = Autothrottle::new(0.5);
+        let throttle = autothrottle.throttle(150.0, 100.0, 1.0);
+        assert!((throttle - 0.6).abs() < 1e-9);
+
+        for _ in 0..100 {
+            autothrottle.throttle(150.0, 100.0, 1.0);
+        }
+
+        assert_eq!(autothrottle.throttle(150.0, 100.0, 1.0), MAX_THROTTLE);
+    }
+}
@@ -0,0 +1,203 @@
+use std::f64::consts::PI;
+
+use crate::{AutotuneFailure, Gains, TuningRule};
+
+/// Output step (fraction of full travel) either side of the trim the relay switches between.
+const RELAY_AMPLITUDE: f64 = 0.1;
+
+/// Error (degrees) the measurement has to cross before the relay switches, so noise around
+/// the setpoint does not chatter it.
+const HYSTERESIS: f64 = 0.5;
+
+/// Oscillations averaged for the result, after the first one has been left to settle.
+const CYCLES: usize = 4;
+
+/// How far (degrees) the axis may swing from the setpoint before the experiment is abandoned.
+const MAX_DEVIATION: f64 = 20.0;
+
+/// Seconds allowed for the oscillation to build up and be measured.
+const MAX_DURATION: f64 = 90.0;
+
+/// Ultimate gain (output per degree) and period (seconds) of an axis: the proportional gain
+/// at which it would oscillate steadily, and the period of that oscillation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelayResult {
+    pub ultimate_gain: f64,
+    pub ultimate_period: f64,
+}
+
+/// Relay feedback experiment on one axis. In place of its control law the axis is driven
+/// hard either side of the trim as the measurement crosses the setpoint, which makes it
+/// oscillate at its ultimate period with an amplitude that gives the ultimate gain.
+pub struct RelayExperiment {
+    setpoint: f64,
+    trim: f64,
+    high: Option<bool>,
+    elapsed: f64,
+    last_switch_up: Option<f64>,
+    min: f64,
+    max: f64,
+    periods: Vec<f64>,
+    amplitudes: Vec<f64>,
+    failure: Option<AutotuneFailure>,
+}
+
+impl RelayExperiment {
+    /// Oscillates about the setpoint (degrees), switching either side of the trim.
+    pub fn new(setpoint: f64, trim: f64) -> Self {
+        Self {
+            setpoint,
+            trim,
+            high: None,
+            elapsed: 0.0,
+            last_switch_up: None,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            periods: Vec::new(),
+            amplitudes: Vec::new(),
+            failure: None,
+        }
+    }
+
+    pub fn trim(&self) -> f64 {
+        self.trim
+    }
+
+    /// Full oscillations measured so far.
+    pub fn cycles(&self) -> usize {
+        self.periods.len().saturating_sub(1)
+    }
+
+    /// Output for a measurement taken dt seconds after the previous one.
+    pub fn update(&mut self, measurement: f64, dt: f64) -> f64 {
+        self.elapsed += dt;
+        self.min = self.min.min(measurement);
+        self.max = self.max.max(measurement);
+
+        let error = self.setpoint - measurement;
+        if error.abs() > MAX_DEVIATION {
+            self.failure = Some(AutotuneFailure::Diverged);
+        } else if self.elapsed > MAX_DURATION {
+            self.failure = Some(AutotuneFailure::TimedOut);
+        }
+
+        match self.high {
+            None => self.high = Some(error >= 0.0),
+            Some(false) if error > HYSTERESIS => {
+                self.high = Some(true);
+
+                // A full cycle runs from one upward switch to the next
+                if let Some(last_switch_up) = self.last_switch_up {
+                    self.periods.push(self.elapsed - last_switch_up);
+                    self.amplitudes.push((self.max - self.min) / 2.0);
+                }
+
+                self.last_switch_up = Some(self.elapsed);
+                self.min = measurement;
+                self.max = measurement;
+            }
+            Some(true) if error < -HYSTERESIS => self.high = Some(false),
+            _ => {}
+        }
+
+        if self.high == Some(true) {
+            self.trim + RELAY_AMPLITUDE
+        } else {
+            self.trim - RELAY_AMPLITUDE
+        }
+    }
+
+    /// The result once enough cycles have been measured, or why there will not be one.
+    pub fn outcome(&self) -> Option<Result<RelayResult, AutotuneFailure>> {
+        if let Some(failure) = self.failure {
+            return Some(Err(failure));
+        }
+
+        if self.cycles() < CYCLES {
+            return None;
+        }
+
+        // The first cycle starts from wherever the axis was and is left out
+        let periods = &self.periods[1..];
+        let amplitudes = &self.amplitudes[1..];
+        let period = periods.iter().sum::<f64>() / periods.len() as f64;
+        let amplitude = amplitudes.iter().sum::<f64>() / amplitudes.len() as f64;
+
+        // Describing function of a relay with hysteresis
+        let amplitude = (amplitude.powi(2) - HYSTERESIS.powi(2)).max(0.0).sqrt();
+        if amplitude <= 0.0 {
+            return Some(Err(AutotuneFailure::NoOscillation));
+        }
+
+        Some(Ok(RelayResult {
+            ultimate_gain: 4.0 * RELAY_AMPLITUDE / (PI * amplitude),
+            ultimate_period: period,
+        }))
+    }
+}
+
+impl TuningRule {
+    /// PID gains, per second, from the ultimate gain and period of an axis.
+    pub fn gains(&self, result: RelayResult) -> Gains {
+        let RelayResult {
+            ultimate_gain,
+            ultimate_period,
+        } = result;
+
+        // Proportional gain, integral time and derivative time
+        let (kp, ti, td) = match self {
+            TuningRule::ZieglerNichols => (
+                0.6 * ultimate_gain,
+                ultimate_period / 2.0,
+                ultimate_period / 8.0,
+            ),
+            // Less aggressive, with more margin against overshoot
+            TuningRule::TyreusLuyben => (
+                ultimate_gain / 2.2,
+                2.2 * ultimate_period,
+                ultimate_period / 6.3,
+            ),
+        };
+
+        Gains {
+            kp,
+            ki: kp / ti,
+            kd: kp * td,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    #[test]
+    fn test_relay_finds_oscillation() {
+        // First order lag behind a dead time
+        let dt = 0.05;
+        let mut delay = VecDeque::from(vec![0.0; 10]);
+        let mut measurement = 0.0;
+        let mut relay = RelayExperiment::new(0.0, 0.0);
+
+        let mut outcome = None;
+        for _ in 0..2000 {
+            delay.push_back(relay.update(measurement, dt));
+            let input = delay.pop_front().unwrap();
+            measurement += (100.0 * input - measurement) / 2.0 * dt;
+
+            outcome = relay.outcome();
+            if outcome.is_some() {
+                break;
+            }
+        }
+
+        let result = outcome.unwrap().unwrap();
+        assert!(result.ultimate_gain > 0.0);
+        assert!(result.ultimate_period > 1.0 && result.ultimate_period < 5.0);
+
+        let gains = TuningRule::TyreusLuyben.gains(result);
+        assert!(gains.kp < TuningRule::ZieglerNichols.gains(result).kp);
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::Instant;
+
+/// Longest step (seconds) a single sample is allowed to integrate over, so a stall in the
+/// telemetry does not wind the integrator up in one go.
+const MAX_DT: f64 = 1.0;
+
+/// Measures the time between samples. Sim time is used when it is known, so the controllers
+/// keep their tuning under time acceleration and stand still while the sim is paused; the
+/// wall clock is used otherwise.
+pub struct SampleClock {
+    last: Option<(Option<f64>, Instant)>,
+}
+
+impl SampleClock {
+    pub fn new() -> Self {
+        Self { last: None }
+    }
+
+    /// Seconds since the previous sample, or None for the first one. Sim time is in seconds.
+    pub fn sample(&mut self, sim_time: Option<f64>, now: Instant) -> Option<f64> {
+        let (last_sim_time, last_now) = self.last.replace((sim_time, now))?;
+        let dt = match (last_sim_time, sim_time) {
+            (Some(last_sim_time), Some(sim_time)) => sim_time - last_sim_time,
+            _ => now.duration_since(last_now).as_secs_f64(),
+        };
+
+        // Sim time goes backwards when a new flight is loaded
+        Some(dt.clamp(0.0, MAX_DT))
+    }
+}
+
+/// Times the steps of a loop that runs more often than sim time is read. Each step is the
+/// wall time since the last one, scaled by how fast sim time ran against the wall clock
+/// between its last two reads, so the steps stay even but still stretch with time
+/// acceleration and stop while the sim is paused. Until sim time is known it runs at the
+/// wall clock.
+pub struct CycleClock {
+    last: Option<Instant>,
+    sim_time: Option<(f64, Instant)>,
+    rate: f64,
+}
+
+impl CycleClock {
+    pub fn new() -> Self {
+        Self {
+            last: None,
+            sim_time: None,
+            rate: 1.0,
+        }
+    }
+
+    /// Sim time (seconds) as read at the given instant.
+    pub fn sim_time(&mut self, seconds: f64, now: Instant) {
+        if let Some((last_seconds, last_now)) = self.sim_time.replace((seconds, now)) {
+            let elapsed = now.duration_since(last_now).as_secs_f64();
+            if elapsed > 0.0 {
+                // Sim time goes backwards when a new flight is loaded
+                self.rate = ((seconds - last_seconds) / elapsed).max(0.0);
+            }
+        }
+    }
+
+    /// Sim seconds since the previous step, or None for the first one.
+    pub fn step(&mut self, now: Instant) -> Option<f64> {
+        let last = self.last.replace(now)?;
+        Some((now.duration_since(last).as_secs_f64() * self.rate).clamp(0.0, MAX_DT))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_sample_clock_prefers_sim_time() {
+        let start = Instant::now();
+        let mut clock = SampleClock::new();
+        assert_eq!(clock.sample(Some(10.0), start), None);
+
+        // Paused
+        let later = start + Duration::from_millis(250);
+        assert_eq!(clock.sample(Some(10.0), later), Some(0.0));
+
+        // Without sim time
+        let dt = clock
+            .sample(None, later + Duration::from_millis(500))
+            .unwrap();
+        assert!((dt - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cycle_clock_follows_sim_rate() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let mut clock = CycleClock::new();
+        assert_eq!(clock.step(start), None);
+        assert!((clock.step(at(100)).unwrap() - 0.1).abs() < 1e-9);
+
+        // Four times as fast, then paused
+        clock.sim_time(10.0, at(100));
+        clock.sim_time(11.0, at(350));
+        assert!((clock.step(at(200)).unwrap() - 0.4).abs() < 1e-9);
+        assert!((clock.step(at(300)).unwrap() - 0.4).abs() < 1e-9);
+        clock.sim_time(11.0, at(600));
+        assert_eq!(clock.step(at(700)), Some(0.0));
+    }
+}
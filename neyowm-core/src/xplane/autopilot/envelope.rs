@@ -0,0 +1,173 @@
+use crate::{EnvelopeLimits, EnvelopeProtection};
+
+/// Pitch (degrees) taken off the target per g the load factor is outside its limits.
+const LOAD_FACTOR_PITCH_GAIN: f64 = 5.0;
+
+/// Sits between the modes and the control surfaces: attitude targets are held inside the
+/// aircraft's bank, pitch and load factor limits, and surface commands are clamped and rate
+/// limited before they are written.
+pub struct Envelope {
+    limits: EnvelopeLimits,
+    protection: EnvelopeProtection,
+    aileron: SurfaceLimiter,
+    elevator: SurfaceLimiter,
+    rudder: SurfaceLimiter,
+}
+
+impl Envelope {
+    pub fn new(limits: EnvelopeLimits) -> Self {
+        Self {
+            limits,
+            protection: EnvelopeProtection::default(),
+            aileron: SurfaceLimiter::new(),
+            elevator: SurfaceLimiter::new(),
+            rudder: SurfaceLimiter::new(),
+        }
+    }
+
+    pub fn set_limits(&mut self, limits: EnvelopeLimits) {
+        self.limits = limits;
+    }
+
+    pub fn limits(&self) -> &EnvelopeLimits {
+        &self.limits
+    }
+
+    pub fn protection(&self) -> EnvelopeProtection {
+        self.protection
+    }
+
+    /// Limits roll and pitch targets (degrees), given the current pitch and normal load factor
+    /// when known. Bank is also held to what the load factor limit allows in a level turn.
+    pub fn attitude(
+        &mut self,
+        roll: f64,
+        pitch: f64,
+        current_pitch: Option<f64>,
+        load_factor: Option<f64>,
+    ) -> (f64, f64) {
+        let max_bank = self
+            .limits
+            .max_bank
+            .min(self.limits.max_load_factor.recip().acos().to_degrees());
+        let limited_roll = roll.clamp(-max_bank, max_bank);
+        let mut limited_pitch = pitch.clamp(self.limits.min_pitch, self.limits.max_pitch);
+
+        self.protection = EnvelopeProtection {
+            bank: limited_roll != roll,
+            pitch: limited_pitch != pitch,
+            load_factor: false,
+        };
+
+        if let (Some(current_pitch), Some(load_factor)) = (current_pitch, load_factor) {
+            if load_factor > self.limits.max_load_factor {
+                // Ease off the pull rather than hold the target
+                limited_pitch = limited_pitch.min(
+                    current_pitch
+                        - LOAD_FACTOR_PITCH_GAIN * (load_factor - self.limits.max_load_factor),
+                );
+                self.protection.load_factor = true;
+            } else if load_factor < self.limits.min_load_factor {
+                limited_pitch = limited_pitch.max(
+                    current_pitch
+                        + LOAD_FACTOR_PITCH_GAIN * (self.limits.min_load_factor - load_factor),
+                );
+                self.protection.load_factor = true;
+            }
+        }
+
+        (limited_roll, limited_pitch)
+    }
+
+    /// Aileron, elevator and rudder commands (fractions of full travel) to write, each within
+    /// the deflection limits and moved no faster than the rate limit allows over dt seconds.
+    pub fn surfaces(
+        &mut self,
+        aileron: Option<f64>,
+        elevator: Option<f64>,
+        rudder: Option<f64>,
+        dt: f64,
+    ) -> (Option<f64>, Option<f64>, Option<f64>) {
+        (
+            self.aileron.limit(&self.limits, aileron, dt),
+            self.elevator.limit(&self.limits, elevator, dt),
+            self.rudder.limit(&self.limits, rudder, dt),
+        )
+    }
+
+    /// Measured surface positions, which the rate limits start from when the autopilot engages.
+    pub fn observe(&mut self, aileron: f64, elevator: f64, rudder: f64) {
+        self.aileron.measured = Some(aileron);
+        self.elevator.measured = Some(elevator);
+        self.rudder.measured = Some(rudder);
+    }
+
+    /// Once the autopilot is no longer flying an attitude, clears the protection flags and
+    /// forgets the last commands so the rate limits restart from the measured positions.
+    pub fn reset(&mut self) {
+        self.protection = EnvelopeProtection::default();
+        self.aileron.value = None;
+        self.elevator.value = None;
+        self.rudder.value = None;
+    }
+}
+
+/// Last commanded and last measured position of a control surface.
+struct SurfaceLimiter {
+    value: Option<f64>,
+    measured: Option<f64>,
+}
+
+impl SurfaceLimiter {
+    fn new() -> Self {
+        Self {
+            value: None,
+            measured: None,
+        }
+    }
+
+    fn limit(&mut self, limits: &EnvelopeLimits, target: Option<f64>, dt: f64) -> Option<f64> {
+        let Some(target) = target else {
+            self.value = None;
+            return None;
+        };
+
+        let target = target.clamp(-limits.max_deflection, limits.max_deflection);
+        let from = self.value.or(self.measured).unwrap_or(target);
+        let step = limits.max_deflection_rate * dt;
+        let value = target.clamp(from - step, from + step);
+
+        self.value = Some(value);
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attitude_limits() {
+        let mut envelope = Envelope::new(EnvelopeLimits::default());
+        assert_eq!(envelope.attitude(10.0, 5.0, None, None), (10.0, 5.0));
+        assert_eq!(envelope.protection(), EnvelopeProtection::default());
+
+        let (roll, pitch) = envelope.attitude(-180.0, 40.0, Some(10.0), Some(2.8));
+        assert_eq!(roll, -35.0);
+        assert!((pitch - 5.0).abs() < 1e-9);
+        assert!(envelope.protection().bank);
+        assert!(envelope.protection().pitch);
+        assert!(envelope.protection().load_factor);
+    }
+
+    #[test]
+    fn test_surface_rate_limit() {
+        let mut envelope = Envelope::new(EnvelopeLimits::default());
+        envelope.observe(0.0, 0.0, 0.0);
+
+        assert_eq!(envelope.surfaces(Some(15.0), None, None, 0.1).0, Some(0.1));
+        assert_eq!(envelope.surfaces(Some(15.0), None, None, 0.1).0, Some(0.2));
+        assert_eq!(envelope.surfaces(Some(0.15), None, None, 0.1).0, Some(0.15));
+        assert_eq!(envelope.surfaces(None, None, None, 0.1).0, None);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{Airport, GlideStatus, GlideTarget, Waypoint};
+
+use super::{
+    navigation::{bearing, distance, FEET_PER_NM},
+    vertical_speed::{MAX_PITCH, MIN_PITCH},
+};
+
+/// Pitch attitude (degrees) commanded per knot above the glide speed.
+const SPEED_GAIN: f64 = 0.5;
+
+/// Rate (degrees per second, per knot of error) at which the pitch trim soaks up a steady
+/// speed error.
+const TRIM_GAIN: f64 = 0.05;
+
+/// Seconds between looking for an airport and reporting the glide.
+const UPDATE_INTERVAL: f64 = 1.0;
+
+/// Shortest runway (feet) worth gliding for.
+const MIN_RUNWAY_LENGTH: f64 = 1500.0;
+
+/// Holds the best glide speed on the elevator and steers for the nearest airport in reach.
+pub struct Glide {
+    trim: f64,
+    airport: Option<usize>,
+    since_update: Option<f64>,
+}
+
+impl Glide {
+    pub fn new(trim: f64) -> Self {
+        Self {
+            trim: trim.clamp(MIN_PITCH, MAX_PITCH),
+            airport: None,
+            since_update: None,
+        }
+    }
+
+    /// Pitch target for the glide speed, nose up when fast. Speeds are in knots and dt in
+    /// seconds.
+    pub fn pitch_target(&mut self, target: f64, airspeed: f64, dt: f64) -> f64 {
+        let error = airspeed - target;
+        self.trim = (self.trim + TRIM_GAIN * error * dt).clamp(MIN_PITCH, MAX_PITCH);
+        (self.trim + SPEED_GAIN * error).clamp(MIN_PITCH, MAX_PITCH)
+    }
+
+    /// Track to the airport being glided for, if there is one.
+    pub fn track(&self, airports: &[Airport], position: &Waypoint) -> Option<f64> {
+        let airport = &airports[self.airport?];
+        Some(bearing(position, &airport.position))
+    }
+
+    /// Looks again for an airport every so often, reporting the glide when it does. The
+    /// airport is kept for as long as it stays in reach, so the aircraft is not turned back and
+    /// forth between two of them. Altitudes are in feet MSL and the ratio is the distance
+    /// covered per unit of height lost.
+    pub fn update(
+        &mut self,
+        airports: &[Airport],
+        position: &Waypoint,
+        altitude: f64,
+        terrain_elevation: Option<f64>,
+        glide_ratio: f64,
+        dt: f64,
+    ) -> Option<GlideStatus> {
+        if let Some(since_update) = &mut self.since_update {
+            *since_update += dt;
+            if *since_update < UPDATE_INTERVAL {
+                return None;
+            }
+        }
+        self.since_update = Some(0.0);
+
+        let margin = |airport: &Airport| {
+            let range = (altitude - airport.elevation) * glide_ratio / FEET_PER_NM;
+            let distance = distance(position, &airport.position);
+            (distance, range - distance)
+        };
+
+        let in_reach = self
+            .airport
+            .is_some_and(|index| margin(&airports[index]).1 >= 0.0);
+        if !in_reach {
+            let candidates = airports
+                .iter()
+                .enumerate()
+                .filter(|(_, airport)| airport.runway_length >= MIN_RUNWAY_LENGTH)
+                .map(|(index, airport)| (index, margin(airport)));
+
+            // The nearest in reach, or failing that the one closest to being in reach
+            let mut nearest: Option<(usize, (f64, f64))> = None;
+            for (index, (distance, margin)) in candidates {
+                let better = match nearest {
+                    None => true,
+                    Some((_, (nearest_distance, nearest_margin))) if nearest_margin >= 0.0 => {
+                        margin >= 0.0 && distance < nearest_distance
+                    }
+                    Some((_, (_, nearest_margin))) => margin > nearest_margin,
+                };
+                if better {
+                    nearest = Some((index, (distance, margin)));
+                }
+            }
+
+            self.airport = nearest.map(|(index, _)| index);
+        }
+
+        Some(GlideStatus {
+            range: terrain_elevation
+                .map(|elevation| (altitude - elevation).max(0.0) * glide_ratio / FEET_PER_NM),
+            airport: self.airport.map(|index| {
+                let airport = &airports[index];
+                let (distance, margin) = margin(airport);
+                GlideTarget {
+                    icao: airport.icao.clone(),
+                    distance,
+                    margin,
+                }
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn airport(icao: &str, latitude: f64, runway_length: f64) -> Airport {
+        Airport {
+            icao: icao.to_string(),
+            name: String::new(),
+            position: Waypoint {
+                latitude,
+                longitude: 0.0,
+            },
+            elevation: 0.0,
+            runway_length,
+        }
+    }
+
+    #[test]
+    fn test_nearest_airport_in_reach() {
+        // 3, 6 and 9 nautical miles north, with the nearest runway too short
+        let airports = vec![
+            airport("NEAR", 0.05, 1000.0),
+            airport("MIDL", 0.1, 3000.0),
+            airport("FAR", 0.15, 3000.0),
+        ];
+        let position = Waypoint {
+            latitude: 0.0,
+            longitude: 0.0,
+        };
+
+        // About 7.4 nautical miles of glide
+        let mut glide = Glide::new(0.0);
+        let status = glide
+            .update(&airports, &position, 5000.0, Some(0.0), 9.0, 0.1)
+            .unwrap();
+        let target = status.airport.unwrap();
+        assert_eq!(target.icao, "MIDL");
+        assert!(target.margin > 1.0 && target.margin < 2.0);
+        assert!((status.range.unwrap() - 7.4).abs() < 0.1);
+
+        // Until it is time to look again
+        assert!(glide
+            .update(&airports, &position, 5000.0, Some(0.0), 9.0, 0.1)
+            .is_none());
+
+        // Out of reach of everything, still heading for the best bet
+        let mut glide = Glide::new(0.0);
+        let status = glide
+            .update(&airports, &position, 2000.0, Some(0.0), 9.0, 0.1)
+            .unwrap();
+        assert_eq!(status.airport.unwrap().icao, "MIDL");
+    }
+}
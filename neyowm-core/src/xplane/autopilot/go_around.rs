@@ -0,0 +1,130 @@
+use crate::GoAroundProfile;
+
+use super::heading;
+
+/// Throttle held until the climb is handed off.
+const GO_AROUND_THROTTLE: f64 = 1.0;
+
+/// Bank limit (degrees) while holding the heading in the go-around.
+const BANK_LIMIT: f64 = 10.0;
+
+/// Climb rate (feet per minute) taken as a positive rate, after which the flaps come up past
+/// the first stage.
+const POSITIVE_RATE: f64 = 100.0;
+
+/// Height (feet) gained at which the climb is handed off, once the flaps are up.
+const HANDOFF_HEIGHT: f64 = 400.0;
+
+/// Full power and a fixed pitch on the heading, with the flaps brought up through the
+/// profile's stages as the speed builds, until the aircraft is clean and climbing away.
+pub struct GoAround {
+    heading: f64,
+    start_altitude: f64,
+    profile: GoAroundProfile,
+    flaps: Option<f64>,
+    stage: usize,
+}
+
+impl GoAround {
+    /// Goes around from the altitude (feet) with the flaps at the given setting, if known.
+    /// Flaps that are not known are left alone.
+    pub fn new(
+        heading: f64,
+        start_altitude: f64,
+        flaps: Option<f64>,
+        profile: GoAroundProfile,
+    ) -> Self {
+        Self {
+            heading,
+            start_altitude,
+            profile,
+            flaps,
+            stage: 0,
+        }
+    }
+
+    pub fn heading(&self) -> f64 {
+        self.heading
+    }
+
+    pub fn throttle(&self) -> f64 {
+        GO_AROUND_THROTTLE
+    }
+
+    pub fn pitch(&self) -> f64 {
+        self.profile.pitch
+    }
+
+    pub fn roll(&self, heading: f64) -> f64 {
+        heading::roll_target(self.heading, heading, BANK_LIMIT)
+    }
+
+    /// Flap setting, moved on to each stage in turn once its speed (knots) is reached and,
+    /// after the first, with a positive climb rate (feet per minute). Never extended further
+    /// than they were, and stages that would are passed over.
+    pub fn flaps(&mut self, airspeed: f64, climb_rate: f64) -> Option<f64> {
+        let mut flaps = self.flaps?;
+
+        while let Some(stage) = self.profile.flaps.get(self.stage) {
+            let ready = airspeed >= stage.speed && (self.stage == 0 || climb_rate > POSITIVE_RATE);
+            if stage.setting < flaps && !ready {
+                break;
+            }
+
+            flaps = flaps.min(stage.setting);
+            self.stage += 1;
+        }
+
+        self.flaps = Some(flaps);
+        self.flaps
+    }
+
+    /// Whether the flaps are up and the aircraft has climbed clear, or reached the altitude
+    /// (feet) it is going around to, so the climb can be handed off.
+    pub fn is_complete(&self, altitude: f64, target: f64) -> bool {
+        let flaps_up = self.flaps.is_none() || self.stage == self.profile.flaps.len();
+        flaps_up && (altitude - self.start_altitude > HANDOFF_HEIGHT || altitude >= target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FlapStage;
+
+    #[test]
+    fn test_flaps_retract_in_stages() {
+        let profile = GoAroundProfile {
+            pitch: 7.0,
+            flaps: vec![
+                FlapStage {
+                    setting: 0.67,
+                    speed: 0.0,
+                },
+                FlapStage {
+                    setting: 0.33,
+                    speed: 60.0,
+                },
+                FlapStage {
+                    setting: 0.0,
+                    speed: 65.0,
+                },
+            ],
+        };
+
+        // Full flap comes straight up to the first stage, then waits for a positive rate
+        let mut go_around = GoAround::new(90.0, 1000.0, Some(1.0), profile.clone());
+        assert_eq!(go_around.flaps(55.0, -200.0), Some(0.67));
+        assert_eq!(go_around.flaps(62.0, -50.0), Some(0.67));
+        assert_eq!(go_around.flaps(62.0, 300.0), Some(0.33));
+        assert!(!go_around.is_complete(1500.0, 3000.0));
+        assert_eq!(go_around.flaps(66.0, 300.0), Some(0.0));
+        assert!(!go_around.is_complete(1300.0, 3000.0));
+        assert!(go_around.is_complete(1500.0, 3000.0));
+
+        // Never extended, and stages already passed do not wait for their speed
+        let mut go_around = GoAround::new(90.0, 1000.0, Some(0.3), profile);
+        assert_eq!(go_around.flaps(50.0, 300.0), Some(0.3));
+        assert_eq!(go_around.flaps(66.0, 300.0), Some(0.0));
+    }
+}
@@ -0,0 +1,46 @@
+use crate::TurnDirection;
+
+/// Bank angle (degrees) commanded per degree of heading error.
+const HEADING_GAIN: f64 = 1.0;
+
+pub const MAX_BANK_LIMIT: f64 = 30.0;
+
+/// Signed difference between two headings in degrees, in the range [-180, 180). Positive
+/// means the target is to the right.
+pub fn heading_error(target: f64, heading: f64) -> f64 {
+    (target - heading + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// +1 for turns to the right, which increase the heading, and -1 for turns to the left.
+pub fn turn_sign(direction: TurnDirection) -> f64 {
+    match direction {
+        TurnDirection::Left => -1.0,
+        TurnDirection::Right => 1.0,
+    }
+}
+
+/// Roll target for turning onto the target heading, limited to the given bank angle.
+pub fn roll_target(target: f64, heading: f64, bank_limit: f64) -> f64 {
+    let bank_limit = bank_limit.abs().min(MAX_BANK_LIMIT);
+    (HEADING_GAIN * heading_error(target, heading)).clamp(-bank_limit, bank_limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_error_wraps() {
+        assert_eq!(heading_error(10.0, 350.0), 20.0);
+        assert_eq!(heading_error(350.0, 10.0), -20.0);
+        assert_eq!(heading_error(90.0, 90.0), 0.0);
+        assert_eq!(heading_error(-90.0, 270.0), 0.0);
+    }
+
+    #[test]
+    fn test_roll_target_is_limited() {
+        assert_eq!(roll_target(180.0, 0.0, 20.0), -20.0);
+        assert_eq!(roll_target(5.0, 355.0, 20.0), 10.0);
+        assert_eq!(roll_target(90.0, 0.0, 45.0), MAX_BANK_LIMIT);
+    }
+}
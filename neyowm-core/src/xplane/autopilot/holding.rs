@@ -0,0 +1,240 @@
+use crate::{HoldLeg, TurnDirection, Waypoint};
+
+use super::{
+    heading::{self, heading_error, turn_sign, MAX_BANK_LIMIT},
+    navigation::{bearing, destination, turn_radius, LegGeometry},
+};
+
+/// Angle (degrees) off the outbound course, toward the holding side, that a teardrop entry is
+/// flown at.
+const TEARDROP_ANGLE: f64 = 30.0;
+
+/// Angle (degrees) off the inbound course, toward the holding side, that a parallel entry turns
+/// back to so it converges on the inbound course.
+const PARALLEL_INTERCEPT_ANGLE: f64 = 45.0;
+
+/// Degrees short of the new course at which a turn hands over to intercepting the next leg,
+/// which rolls out onto it.
+const ROLL_OUT: f64 = 30.0;
+
+/// How the aircraft joins the hold, from the track it reaches the fix on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoldEntry {
+    // Turn straight onto the outbound leg at the fix
+    Direct,
+    // Out on the holding side of the outbound course, then turn inbound
+    Teardrop,
+    // Out along the inbound course, then turn back the other way to intercept it
+    Parallel,
+}
+
+impl HoldEntry {
+    /// Standard entry for a track to the fix (degrees true), by the sectors either side of a
+    /// line through the fix 70 degrees off the inbound course.
+    pub fn for_track(track: f64, inbound_course: f64, direction: TurnDirection) -> Self {
+        // Positive when the track is turned off the inbound course the way the hold turns
+        let angle = heading_error(track, inbound_course) * turn_sign(direction);
+        if angle > 110.0 {
+            HoldEntry::Teardrop
+        } else if angle < -70.0 {
+            HoldEntry::Parallel
+        } else {
+            HoldEntry::Direct
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Leg {
+    Outbound,
+    Inbound,
+}
+
+#[derive(Debug, Clone)]
+enum Phase {
+    // Direct to the fix from where the hold was engaged
+    ToFix {
+        from: Waypoint,
+    },
+    // Leg flown out from the fix by the teardrop and parallel entries
+    Entry {
+        to: Waypoint,
+    },
+    // Banked to the limit one way until the new course is nearly reached, then onto the leg
+    Turn {
+        sign: f64,
+        remaining: f64,
+        heading: f64,
+        then: Leg,
+    },
+    Outbound {
+        from: Waypoint,
+        to: Waypoint,
+    },
+    Inbound {
+        from: Waypoint,
+    },
+}
+
+/// Racetrack hold: turns at the fix onto an outbound leg on the holding side, then back onto
+/// the inbound course to the fix, after joining it by the standard entry.
+pub struct Holding {
+    fix: Waypoint,
+    inbound_course: f64,
+    leg: HoldLeg,
+    sign: f64,
+    entry: HoldEntry,
+    phase: Phase,
+}
+
+impl Holding {
+    pub fn new(
+        position: Waypoint,
+        fix: Waypoint,
+        inbound_course: f64,
+        leg: HoldLeg,
+        direction: TurnDirection,
+    ) -> Self {
+        Self {
+            entry: HoldEntry::for_track(bearing(&position, &fix), inbound_course, direction),
+            fix,
+            inbound_course,
+            leg,
+            sign: turn_sign(direction),
+            phase: Phase::ToFix { from: position },
+        }
+    }
+
+    /// Roll target from the given position, moving on to the next part of the hold as each
+    /// is completed. Speed is in knots.
+    pub fn roll_target(
+        &mut self,
+        position: &Waypoint,
+        heading: f64,
+        speed: f64,
+        bank_limit: f64,
+    ) -> f64 {
+        let bank_limit = bank_limit.abs().min(MAX_BANK_LIMIT);
+        let outbound_course = (self.inbound_course + 180.0).rem_euclid(360.0);
+        let length = match self.leg {
+            HoldLeg::Time { minutes } => speed * minutes / 60.0,
+            HoldLeg::Distance { nm } => nm,
+        };
+
+        loop {
+            let (from, to) = match &mut self.phase {
+                Phase::ToFix { from } => (from.clone(), self.fix.clone()),
+                Phase::Entry { to } => (self.fix.clone(), to.clone()),
+                Phase::Outbound { from, to } => (from.clone(), to.clone()),
+                Phase::Inbound { from } => (from.clone(), self.fix.clone()),
+                Phase::Turn {
+                    sign,
+                    remaining,
+                    heading: last_heading,
+                    then,
+                } => {
+                    *remaining -= heading_error(heading, *last_heading) * *sign;
+                    *last_heading = heading;
+                    if *remaining > ROLL_OUT {
+                        return *sign * bank_limit;
+                    }
+
+                    self.phase = match *then {
+                        Leg::Outbound => {
+                            // Offset from the inbound course by the width of the turn
+                            let from = destination(
+                                &self.fix,
+                                self.inbound_course + self.sign * 90.0,
+                                2.0 * turn_radius(speed, bank_limit),
+                            );
+                            Phase::Outbound {
+                                to: destination(&from, outbound_course, length),
+                                from,
+                            }
+                        }
+                        Leg::Inbound => Phase::Inbound {
+                            from: destination(&self.fix, outbound_course, length),
+                        },
+                    };
+                    continue;
+                }
+            };
+
+            let leg = LegGeometry::new(&from, &to, position);
+            if leg.distance_to_go > 0.0 {
+                return heading::roll_target(leg.intercept_track(), heading, bank_limit);
+            }
+
+            self.phase = match (&self.phase, self.entry) {
+                (Phase::ToFix { .. }, HoldEntry::Teardrop) => Phase::Entry {
+                    to: destination(
+                        &self.fix,
+                        outbound_course - self.sign * TEARDROP_ANGLE,
+                        length,
+                    ),
+                },
+                (Phase::ToFix { .. }, HoldEntry::Parallel) => Phase::Entry {
+                    to: destination(&self.fix, outbound_course, length),
+                },
+                (Phase::Entry { .. }, HoldEntry::Parallel) => turn(
+                    -self.sign,
+                    self.inbound_course + self.sign * PARALLEL_INTERCEPT_ANGLE,
+                    heading,
+                    Leg::Inbound,
+                ),
+                (Phase::Entry { .. }, _) | (Phase::Outbound { .. }, _) => {
+                    turn(self.sign, self.inbound_course, heading, Leg::Inbound)
+                }
+                (Phase::ToFix { .. }, HoldEntry::Direct) | (Phase::Inbound { .. }, _) => {
+                    turn(self.sign, outbound_course, heading, Leg::Outbound)
+                }
+                (Phase::Turn { .. }, _) => unreachable!("turns are handled above"),
+            };
+        }
+    }
+}
+
+fn turn(sign: f64, course: f64, heading: f64, then: Leg) -> Phase {
+    let remaining = ((course - heading) * sign).rem_euclid(360.0);
+    Phase::Turn {
+        sign,
+        // Already just past the course, so leave the intercept to take out the difference
+        // rather than going all the way round
+        remaining: if remaining > 360.0 - ROLL_OUT {
+            0.0
+        } else {
+            remaining
+        },
+        heading,
+        then,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_sectors() {
+        // Right turns: direct from 290 round through 360 to 110, teardrop from 110 to 180
+        // and parallel from 180 to 290
+        let entry = |track| HoldEntry::for_track(track, 360.0, TurnDirection::Right);
+        assert_eq!(entry(291.0), HoldEntry::Direct);
+        assert_eq!(entry(10.0), HoldEntry::Direct);
+        assert_eq!(entry(109.0), HoldEntry::Direct);
+        assert_eq!(entry(111.0), HoldEntry::Teardrop);
+        assert_eq!(entry(179.0), HoldEntry::Teardrop);
+        assert_eq!(entry(181.0), HoldEntry::Parallel);
+        assert_eq!(entry(289.0), HoldEntry::Parallel);
+
+        // Mirrored for left turns: teardrop from 180 to 250 and parallel from 070 to 180
+        let entry = |track| HoldEntry::for_track(track, 360.0, TurnDirection::Left);
+        assert_eq!(entry(251.0), HoldEntry::Direct);
+        assert_eq!(entry(350.0), HoldEntry::Direct);
+        assert_eq!(entry(69.0), HoldEntry::Direct);
+        assert_eq!(entry(71.0), HoldEntry::Parallel);
+        assert_eq!(entry(179.0), HoldEntry::Parallel);
+        assert_eq!(entry(181.0), HoldEntry::Teardrop);
+        assert_eq!(entry(249.0), HoldEntry::Teardrop);
+    }
+}
@@ -0,0 +1,129 @@
+use super::{approach::Nav1, heading::heading_error};
+
+/// Time constant (seconds) of the exponential flare: sink rate is commanded in proportion to
+/// height, so it bleeds off as the wheels approach the runway.
+const FLARE_TIME_CONSTANT: f64 = 4.0;
+
+/// Sink rate (feet per minute) the flare settles on so the aircraft does touch down.
+const TOUCHDOWN_SINK_RATE: f64 = 120.0;
+
+/// How fast the throttle is pulled to idle once the flare begins, in full travel per second.
+const RETARD_RATE: f64 = 0.2;
+
+/// Rudder (fraction of full travel) commanded per dot of localizer deflection on the rollout.
+const ROLLOUT_LOCALIZER_GAIN: f64 = 0.2;
+
+/// Rudder (fraction of full travel) commanded per degree of heading error on the rollout.
+const ROLLOUT_HEADING_GAIN: f64 = 0.05;
+
+/// Indicated airspeed (knots) below which the rollout is complete.
+const ROLLOUT_COMPLETE_SPEED: f64 = 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LandingPhase {
+    Armed,
+    Flare,
+    Rollout,
+}
+
+/// Flare, touchdown and rollout at the bottom of a coupled approach.
+pub struct Landing {
+    flare_height: f64,
+    phase: LandingPhase,
+    throttle: f64,
+}
+
+impl Landing {
+    /// Flare height is in feet above the terrain.
+    pub fn new(flare_height: f64) -> Self {
+        Self {
+            flare_height,
+            phase: LandingPhase::Armed,
+            throttle: 0.0,
+        }
+    }
+
+    pub fn phase(&self) -> LandingPhase {
+        self.phase
+    }
+
+    /// Throttle is the current lever position, which the retard starts from.
+    pub fn update(&mut self, height: f64, on_ground: bool, throttle: f64) {
+        match self.phase {
+            LandingPhase::Armed if on_ground || height < self.flare_height => {
+                self.phase = LandingPhase::Flare;
+                self.throttle = throttle;
+            }
+            LandingPhase::Flare if on_ground => {
+                self.phase = LandingPhase::Rollout;
+            }
+            _ => {}
+        }
+    }
+
+    /// Climb rate (feet per minute) to fly through the flare, given height above the terrain.
+    pub fn climb_rate(&self, height: f64) -> Option<f64> {
+        match self.phase {
+            LandingPhase::Flare => {
+                Some(-(height.max(0.0) / FLARE_TIME_CONSTANT * 60.0).max(TOUCHDOWN_SINK_RATE))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn throttle(&mut self, dt: f64) -> Option<f64> {
+        match self.phase {
+            LandingPhase::Armed => None,
+            LandingPhase::Flare | LandingPhase::Rollout => {
+                self.throttle = (self.throttle - RETARD_RATE * dt).max(0.0);
+                Some(self.throttle)
+            }
+        }
+    }
+
+    /// Rudder to hold the centerline once on the ground, steering on the localizer and its
+    /// course (degrees true).
+    pub fn rudder(&self, nav: &Nav1, course: f64, heading: f64) -> Option<f64> {
+        if self.phase != LandingPhase::Rollout {
+            return None;
+        }
+
+        let localizer = if nav.localizer_valid {
+            nav.localizer
+        } else {
+            0.0
+        };
+
+        let rudder = ROLLOUT_LOCALIZER_GAIN * localizer
+            + ROLLOUT_HEADING_GAIN * heading_error(course, heading);
+        Some(rudder.clamp(-1.0, 1.0))
+    }
+
+    pub fn is_complete(&self, airspeed: f64) -> bool {
+        self.phase == LandingPhase::Rollout && airspeed < ROLLOUT_COMPLETE_SPEED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flare_to_rollout() {
+        let mut landing = Landing::new(30.0);
+        landing.update(100.0, false, 0.4);
+        assert_eq!(landing.phase(), LandingPhase::Armed);
+        assert_eq!(landing.throttle(1.0), None);
+
+        landing.update(25.0, false, 0.4);
+        assert_eq!(landing.phase(), LandingPhase::Flare);
+        assert_eq!(landing.climb_rate(25.0), Some(-375.0));
+        assert_eq!(landing.climb_rate(2.0), Some(-TOUCHDOWN_SINK_RATE));
+        assert!((landing.throttle(1.0).unwrap() - 0.2).abs() < 1e-9);
+
+        landing.update(3.0, true, 0.2);
+        assert_eq!(landing.phase(), LandingPhase::Rollout);
+        assert!(!landing.is_complete(60.0));
+        assert!(landing.is_complete(20.0));
+    }
+}
@@ -0,0 +1,1610 @@
+mod altitude;
+mod altitude_capture;
+mod approach;
+mod autothrottle;
+mod autotune;
+mod clock;
+mod envelope;
+mod glide;
+mod go_around;
+mod heading;
+mod holding;
+mod landing;
+pub(crate) mod navigation;
+mod orbit;
+mod pilot_override;
+mod recovery;
+mod takeoff;
+mod terrain_follow;
+mod vertical_speed;
+mod watchdog;
+mod yaw_damper;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use xplaneconnect::{SetControlSurface, XPlaneConnection};
+
+use crate::{
+    server::Bridge, AircraftProfile, Airport, AutopilotAlert, AutopilotMode, AutopilotStatus,
+    AutothrottleMode, AutotuneFailure, AutotuneStatus, ClientBoundMessage, ControlAxis, ControlLaw,
+    ControlLawConfig, ControllerInternals, EnvelopeLimits, FlightDirectorCue, GoAroundProfile,
+    LateralMode, ProfileSelection, ProfileStatus, RecoveryStatus, ServerBoundMessage,
+    TelemetryInput, TelemetryUpdate, ThrustMode, TuningRule, Upset, VerticalMode, Waypoint,
+};
+
+use self::{
+    altitude::FEET_PER_METER,
+    altitude_capture::AltitudeCapture,
+    approach::{Approach, CaptureState, Nav1},
+    autothrottle::Autothrottle,
+    autotune::{RelayExperiment, RelayResult},
+    clock::{CycleClock, SampleClock},
+    envelope::Envelope,
+    glide::Glide,
+    go_around::GoAround,
+    holding::Holding,
+    landing::LandingPhase,
+    navigation::Lnav,
+    pilot_override::{Overridden, PilotOverride},
+    recovery::{Recovery, RecoveryControls},
+    takeoff::Takeoff,
+    terrain_follow::TerrainFollow,
+    vertical_speed::{ClimbRateEstimator, VerticalSpeedHold},
+    watchdog::Watchdog,
+    yaw_damper::YawDamper,
+};
+
+/// Fastest the roll target moves toward what the mode asks for, in degrees per second.
+const ROLL_RATE_LIMIT: f64 = 5.0;
+
+/// Fastest the pitch target moves toward what the mode asks for, in degrees per second.
+const PITCH_RATE_LIMIT: f64 = 3.0;
+
+/// Bank limit (degrees) of the heading hold a takeoff or a go-around hands off to.
+const HANDOFF_BANK_LIMIT: f64 = 20.0;
+
+/// Airspeed (knots) assumed for turn anticipation until airspeed telemetry arrives, unless
+/// an aircraft profile gives a cruise speed.
+const DEFAULT_AIRSPEED: f64 = 100.0;
+
+/// Speed (knots) a recovery adds power below, unless an aircraft profile gives a best rate of
+/// climb speed.
+const DEFAULT_CLIMB_SPEED: f64 = 75.0;
+
+/// Speed (knots) a stable approach is flown at, unless an aircraft profile gives one.
+const DEFAULT_APPROACH_SPEED: f64 = 65.0;
+
+/// Best glide speed (knots) and glide ratio of a light single, unless an aircraft profile
+/// gives them.
+const DEFAULT_GLIDE_SPEED: f64 = 70.0;
+const DEFAULT_GLIDE_RATIO: f64 = 9.0;
+
+pub struct Autopilot {
+    connection: Arc<Mutex<XPlaneConnection>>,
+    limits: EnvelopeLimits,
+    roll_law: Box<dyn ControlLaw>,
+    pitch_law: Box<dyn ControlLaw>,
+    profiles: Vec<AircraftProfile>,
+    airports: Vec<Airport>,
+}
+
+impl Autopilot {
+    pub fn new(connection: Arc<Mutex<XPlaneConnection>>) -> Self {
+        Self {
+            connection,
+            limits: EnvelopeLimits::default(),
+            roll_law: ControlLawConfig::default().build(),
+            pitch_law: ControlLawConfig::default().build(),
+            profiles: Vec::new(),
+            airports: Vec::new(),
+        }
+    }
+
+    pub fn with_limits(mut self, limits: EnvelopeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Control laws flying the roll and pitch axes, from degrees of error to fractions of
+    /// aileron and elevator travel.
+    pub fn with_control_laws(
+        mut self,
+        roll_law: Box<dyn ControlLaw>,
+        pitch_law: Box<dyn ControlLaw>,
+    ) -> Self {
+        self.roll_law = roll_law;
+        self.pitch_law = pitch_law;
+        self
+    }
+
+    /// Aircraft profiles to choose from, by the loaded aircraft or as selected. Once one is
+    /// selected its gains and limits replace the ones given to the builder; the first is used
+    /// when none matches the aircraft.
+    pub fn with_profiles(mut self, profiles: Vec<AircraftProfile>) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    /// Airports to glide for with an engine out.
+    pub fn with_airports(mut self, airports: Vec<Airport>) -> Self {
+        self.airports = airports;
+        self
+    }
+
+    pub fn run(mut self, bridge: Bridge, interval: Duration) {
+        self.pitch_law.set_setpoint(2.0);
+        let mut state = State {
+            mode: AutopilotMode::Off,
+            roll: AxisState::new(self.roll_law),
+            pitch: AxisState::new(self.pitch_law),
+            vertical_speed_hold: VerticalSpeedHold::new(2.0),
+            altitude_capture: None,
+            yaw_damper: YawDamper::new(0.0),
+            envelope: Envelope::new(self.limits.clone()),
+            pilot_override: PilotOverride::new(),
+            watchdog: Watchdog::new(),
+            last_control: None,
+            throttle_from_mode: false,
+            flight_director: false,
+            cue: None,
+            upset_protection: false,
+            recovery: None,
+            stall_warning: false,
+            sim_time: None,
+            heading: None,
+            roll_angle: None,
+            pitch_angle: None,
+            control_positions: None,
+            load_factor: None,
+            sideslip: None,
+            climb_rate: ClimbRateEstimator::new(),
+            position_clock: SampleClock::new(),
+            cycle_clock: CycleClock::new(),
+            position: None,
+            lnav: None,
+            holding: None,
+            nav1: None,
+            approach: None,
+            takeoff: None,
+            go_around: None,
+            flaps: None,
+            terrain_elevation: None,
+            terrain_ahead: Vec::new(),
+            terrain_follow: None,
+            on_ground: false,
+            autothrottle_mode: AutothrottleMode::Off,
+            autothrottle: Autothrottle::new(0.0),
+            airspeed: None,
+            throttle: None,
+            status: AutopilotStatus::default(),
+            autotune: None,
+            profiles: self.profiles,
+            profile: None,
+            profile_selection: ProfileSelection::Automatic,
+            aircraft: None,
+            default_airspeed: DEFAULT_AIRSPEED,
+            climb_speed: DEFAULT_CLIMB_SPEED,
+            approach_speed: DEFAULT_APPROACH_SPEED,
+            go_around_profile: GoAroundProfile::default(),
+            airports: self.airports,
+            glide: None,
+            glide_speed: DEFAULT_GLIDE_SPEED,
+            glide_ratio: DEFAULT_GLIDE_RATIO,
+        };
+
+        bridge.recv_with_interval(interval, |queue, tx| {
+            for msg in queue.iter().cloned() {
+                match msg {
+                    ClientBoundMessage::Shutdown => break,
+                    ClientBoundMessage::SetAutopilotMode(mode) => {
+                        state.set_mode(mode, tx);
+                        state.engage();
+                    }
+                    ClientBoundMessage::SetFlightDirector(on) if on != state.flight_director => {
+                        // Picks the controls up from the pilot, or hands them back, the same
+                        // way as engaging a mode
+                        state.stop_autotune(tx);
+                        state.flight_director = on;
+                        state.envelope.reset();
+                        state.engage();
+                    }
+                    ClientBoundMessage::SetUpsetProtection(on) => {
+                        state.upset_protection = on;
+                    }
+                    ClientBoundMessage::StartRecovery => {
+                        let upset = state
+                            .roll_angle
+                            .zip(state.pitch_angle)
+                            .and_then(|(roll, pitch)| {
+                                Upset::detect(roll, pitch, state.stall_warning)
+                            })
+                            .unwrap_or(Upset::Requested);
+                        state.start_recovery(upset, tx);
+                    }
+                    ClientBoundMessage::SetGains(axis, gains) => {
+                        match axis {
+                            ControlAxis::Roll => state.roll.law.set_gains(gains),
+                            ControlAxis::Pitch => state.pitch.law.set_gains(gains),
+                        };
+                    }
+                    ClientBoundMessage::StartAutotune(axis, rule) => {
+                        state.start_autotune(axis, rule, tx);
+                    }
+                    ClientBoundMessage::StopAutotune => {
+                        state.stop_autotune(tx);
+                    }
+                    ClientBoundMessage::SelectProfile(selection) => {
+                        state.profile_selection = selection;
+                        state.select_profile(tx);
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Aircraft { icao })
+                        if state.aircraft.as_ref() != Some(&icao) =>
+                    {
+                        state.aircraft = Some(icao);
+                        state.select_profile(tx);
+                    }
+                    ClientBoundMessage::SetAutothrottleMode(mode) => {
+                        state.autothrottle_mode = mode;
+                        state.autothrottle = Autothrottle::new(state.throttle.unwrap_or(0.0));
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::SimTime { seconds }) => {
+                        state.sim_time = Some(seconds);
+                        state.cycle_clock.sim_time(seconds, Instant::now());
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Orientation {
+                        roll,
+                        pitch,
+                        yaw,
+                    }) => {
+                        let now = Instant::now();
+                        state.roll.update(roll, state.sim_time, now);
+                        state.pitch.update(pitch, state.sim_time, now);
+                        if state.flies_attitude() {
+                            for internals in [
+                                state.roll.internals(ControlAxis::Roll, roll),
+                                state.pitch.internals(ControlAxis::Pitch, pitch),
+                            ]
+                            .into_iter()
+                            .flatten()
+                            {
+                                _ = tx.send(ServerBoundMessage::Broadcast(
+                                    ClientBoundMessage::ControllerInternals(internals),
+                                ));
+                            }
+                        }
+
+                        state.check_autotune(tx);
+
+                        state.heading = Some(yaw);
+                        state.roll_angle = Some(roll);
+                        state.pitch_angle = Some(pitch);
+                        state.watchdog.received(TelemetryInput::Attitude, now);
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Position {
+                        latitude,
+                        longitude,
+                        altitude,
+                    }) => {
+                        let now = Instant::now();
+                        let dt = state.position_clock.sample(state.sim_time, now);
+
+                        state
+                            .climb_rate
+                            .update(altitude * FEET_PER_METER, dt.unwrap_or(0.0));
+                        state.watchdog.received(TelemetryInput::Position, now);
+                        state.position = Some(Waypoint {
+                            latitude,
+                            longitude,
+                        });
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Terrain {
+                        elevation,
+                        ..
+                    }) => {
+                        state
+                            .watchdog
+                            .received(TelemetryInput::Terrain, Instant::now());
+                        state.terrain_elevation = Some(elevation * FEET_PER_METER);
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::TerrainProfile {
+                        samples,
+                        ..
+                    }) => {
+                        state
+                            .watchdog
+                            .received(TelemetryInput::TerrainAhead, Instant::now());
+                        state.terrain_ahead = samples
+                            .iter()
+                            .map(|sample| (sample.distance, sample.elevation * FEET_PER_METER))
+                            .collect();
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Ground { on_ground }) => {
+                        state.on_ground = on_ground;
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Sideslip { beta }) => {
+                        state.sideslip = Some(beta);
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::LoadFactor { normal }) => {
+                        state.load_factor = Some(normal);
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::StallWarning {
+                        warning,
+                    }) => {
+                        state.stall_warning = warning;
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Control {
+                        aileron,
+                        elevator,
+                        rudder,
+                        throttle,
+                        flaps,
+                        ..
+                    }) => {
+                        let now = Instant::now();
+                        let dt = state
+                            .last_control
+                            .map(|last_control| now.duration_since(last_control).as_secs_f64())
+                            .unwrap_or(0.0);
+
+                        state.last_control = Some(now);
+                        state.envelope.observe(aileron, elevator, rudder);
+                        state.control_positions = Some(ControlPositions {
+                            aileron,
+                            elevator,
+                            rudder,
+                        });
+                        state.throttle = Some(throttle);
+                        state.flaps = Some(flaps);
+
+                        match state
+                            .pilot_override
+                            .update(aileron, elevator, rudder, throttle, dt)
+                        {
+                            Some(Overridden::FlightControls)
+                                if state.mode != AutopilotMode::Off || state.recovery.is_some() =>
+                            {
+                                // Disarmed too, or it would take straight back over
+                                if state.recovery.is_some() {
+                                    state.upset_protection = false;
+                                }
+                                state.disengage_autopilot(tx);
+                                broadcast_alert(tx, AutopilotAlert::PilotOverride);
+                            }
+                            Some(Overridden::Throttle) if state.throttle_from_mode => {
+                                // The mode itself was driving the throttle, e.g. on a takeoff
+                                state.disengage_autopilot(tx);
+                                broadcast_alert(tx, AutopilotAlert::ThrottleOverride);
+                            }
+                            Some(Overridden::Throttle)
+                                if state.autothrottle_mode != AutothrottleMode::Off =>
+                            {
+                                state.disengage_autothrottle(tx);
+                                broadcast_alert(tx, AutopilotAlert::ThrottleOverride);
+                            }
+                            _ => {}
+                        }
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Nav1 {
+                        localizer,
+                        glideslope,
+                        localizer_valid,
+                        glideslope_valid,
+                    }) => {
+                        state
+                            .watchdog
+                            .received(TelemetryInput::Nav1, Instant::now());
+                        state.nav1 = Some(Nav1 {
+                            localizer,
+                            glideslope,
+                            localizer_valid,
+                            glideslope_valid,
+                        });
+                    }
+                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Airspeed {
+                        indicated,
+                    }) => {
+                        state.airspeed = Some(indicated);
+                        state
+                            .watchdog
+                            .received(TelemetryInput::Airspeed, Instant::now());
+                    }
+                    ClientBoundMessage::UpdateConnectionStatus(status) => {
+                        state.watchdog.connection(&status);
+                    }
+                    _ => {}
+                }
+            }
+
+            if state.profile.is_none() {
+                state.select_profile(tx);
+            }
+
+            // Every loop and timer below steps by the same sim time, whatever the interval
+            let dt = state
+                .cycle_clock
+                .step(Instant::now())
+                .unwrap_or(interval.as_secs_f64());
+
+            state.check_telemetry(tx);
+            state.check_upset(tx);
+
+            // A recovery flies the surfaces itself, in place of the modes
+            let recovery = state.recovery_controls(dt, tx);
+            let mut guidance = match recovery {
+                Some(_) => None,
+                None => state.guidance(dt, tx),
+            };
+            if let Some(guidance) = &mut guidance {
+                // Modes that steer with the rudder on the ground take priority over the damper
+                if guidance.rudder.is_none() {
+                    guidance.rudder = state.yaw_damper_rudder(dt);
+                }
+
+                (guidance.roll, guidance.pitch) = state.envelope.attitude(
+                    guidance.roll,
+                    guidance.pitch,
+                    state.pitch_angle,
+                    state.load_factor,
+                );
+            } else if recovery.is_none() {
+                state.envelope.reset();
+            }
+
+            // With the flight director on the modes only steer the cues, and the pilot flies
+            let flown = guidance.as_ref().filter(|_| !state.flight_director);
+
+            let mode_throttle = recovery
+                .and_then(|recovery| recovery.throttle)
+                .or(flown.and_then(|guidance| guidance.throttle));
+            state.throttle_from_mode = mode_throttle.is_some();
+            let throttle = match mode_throttle {
+                Some(throttle) => Some(throttle),
+                None => state.throttle_target(dt),
+            };
+
+            let status = state.status();
+            if status != state.status {
+                state.status = status.clone();
+                _ = tx.send(ServerBoundMessage::Broadcast(
+                    ClientBoundMessage::AutopilotStatus(status),
+                ));
+            }
+
+            if let Some(guidance) = &guidance {
+                state.roll.ramp_setpoint(guidance.roll, ROLL_RATE_LIMIT, dt);
+                state
+                    .pitch
+                    .ramp_setpoint(guidance.pitch, PITCH_RATE_LIMIT, dt);
+            }
+
+            let cue = guidance
+                .as_ref()
+                .filter(|_| state.flight_director)
+                .map(|_| FlightDirectorCue {
+                    roll: state.roll.law.setpoint(),
+                    pitch: state.pitch.law.setpoint(),
+                });
+            if cue.is_some() || state.cue.is_some() {
+                state.cue = cue;
+                _ = tx.send(ServerBoundMessage::Broadcast(
+                    ClientBoundMessage::FlightDirector(cue),
+                ));
+            }
+
+            if recovery.is_none() && flown.is_none() && throttle.is_none() {
+                state
+                    .pilot_override
+                    .commanded(&SetControlSurface::default());
+                return;
+            }
+
+            let Ok(connection) = self.connection.lock() else {
+                return;
+            };
+
+            let (aileron, elevator, rudder) = match recovery {
+                Some(recovery) => {
+                    let rudder = state.yaw_damper_rudder(dt);
+                    state.envelope.surfaces(
+                        Some(recovery.aileron),
+                        Some(recovery.elevator),
+                        rudder,
+                        dt,
+                    )
+                }
+                None => state.envelope.surfaces(
+                    flown.and(state.roll.finite_value()),
+                    flown.and(state.pitch.finite_value()),
+                    flown.and_then(|guidance| guidance.rudder),
+                    dt,
+                ),
+            };
+
+            let controls = SetControlSurface {
+                aileron,
+                elevator,
+                rudder,
+                throttle,
+                flaps: flown.and_then(|guidance| guidance.flaps),
+                ..Default::default()
+            };
+
+            state.pilot_override.commanded(&controls);
+            _ = connection.write_controls(controls);
+        });
+    }
+}
+
+pub struct State {
+    mode: AutopilotMode,
+    roll: AxisState,
+    pitch: AxisState,
+    vertical_speed_hold: VerticalSpeedHold,
+    altitude_capture: Option<AltitudeCapture>,
+    yaw_damper: YawDamper,
+    envelope: Envelope,
+    pilot_override: PilotOverride,
+    watchdog: Watchdog,
+    last_control: Option<Instant>,
+    throttle_from_mode: bool,
+    flight_director: bool,
+    cue: Option<FlightDirectorCue>,
+    upset_protection: bool,
+    recovery: Option<Recovery>,
+    stall_warning: bool,
+    sim_time: Option<f64>,
+    heading: Option<f64>,
+    roll_angle: Option<f64>,
+    pitch_angle: Option<f64>,
+    control_positions: Option<ControlPositions>,
+    load_factor: Option<f64>,
+    sideslip: Option<f64>,
+    climb_rate: ClimbRateEstimator,
+    position_clock: SampleClock,
+    cycle_clock: CycleClock,
+    position: Option<Waypoint>,
+    lnav: Option<Lnav>,
+    holding: Option<Holding>,
+    nav1: Option<Nav1>,
+    approach: Option<Approach>,
+    takeoff: Option<Takeoff>,
+    go_around: Option<GoAround>,
+    flaps: Option<f64>,
+    terrain_elevation: Option<f64>,
+    // Nautical miles ahead along the track and the elevation there in feet MSL
+    terrain_ahead: Vec<(f64, f64)>,
+    terrain_follow: Option<TerrainFollow>,
+    on_ground: bool,
+    autothrottle_mode: AutothrottleMode,
+    autothrottle: Autothrottle,
+    airspeed: Option<f64>,
+    throttle: Option<f64>,
+    status: AutopilotStatus,
+    autotune: Option<(ControlAxis, TuningRule)>,
+    profiles: Vec<AircraftProfile>,
+    profile: Option<usize>,
+    profile_selection: ProfileSelection,
+    aircraft: Option<String>,
+    default_airspeed: f64,
+    climb_speed: f64,
+    approach_speed: f64,
+    go_around_profile: GoAroundProfile,
+    airports: Vec<Airport>,
+    glide: Option<Glide>,
+    glide_speed: f64,
+    glide_ratio: f64,
+}
+
+impl State {
+    /// Picks up from wherever the aircraft and its controls are: the attitude loops start
+    /// out targeting the current attitude with the current control positions as trim, and
+    /// the targets are then ramped to what the new mode asks for.
+    fn engage(&mut self) {
+        let controls = self.control_positions;
+        self.roll
+            .engage(self.roll_angle, controls.map(|controls| controls.aileron));
+        self.pitch
+            .engage(self.pitch_angle, controls.map(|controls| controls.elevator));
+
+        self.vertical_speed_hold = VerticalSpeedHold::new(self.pitch.law.setpoint());
+        self.yaw_damper = YawDamper::new(controls.map_or(0.0, |controls| controls.rudder));
+    }
+
+    /// Whether the attitude loops are flying the aircraft, rather than sitting idle or only
+    /// cueing the flight director.
+    fn flies_attitude(&self) -> bool {
+        self.mode != AutopilotMode::Off && !self.flight_director && self.recovery.is_none()
+    }
+
+    fn guidance(
+        &mut self,
+        dt: f64,
+        tx: &crossbeam_channel::Sender<ServerBoundMessage>,
+    ) -> Option<Guidance> {
+        let (roll, pitch) = match self.mode.clone() {
+            AutopilotMode::Off => return None,
+            AutopilotMode::Hold { roll, pitch } => (roll, pitch),
+            AutopilotMode::AltitudeHold { roll, altitude } => {
+                (roll, self.altitude_pitch_target(altitude, dt)?)
+            }
+            AutopilotMode::Heading {
+                heading,
+                bank_limit,
+                altitude,
+            } => {
+                let current_heading = self.heading?;
+                (
+                    heading::roll_target(heading, current_heading, bank_limit),
+                    self.altitude_pitch_target(altitude, dt)?,
+                )
+            }
+            AutopilotMode::Lnav {
+                waypoints,
+                bank_limit,
+                altitude,
+            } => {
+                let position = self.position.clone()?;
+                let current_heading = self.heading?;
+                let lnav = self
+                    .lnav
+                    .get_or_insert_with(|| Lnav::new(position.clone(), waypoints));
+                let track =
+                    lnav.desired_track(&position, self.airspeed.unwrap_or(self.default_airspeed))?;
+
+                (
+                    heading::roll_target(track, current_heading, bank_limit),
+                    self.altitude_pitch_target(altitude, dt)?,
+                )
+            }
+            AutopilotMode::Orbit {
+                center,
+                radius,
+                direction,
+                bank_limit,
+                altitude,
+            } => {
+                let position = self.position.clone()?;
+                let current_heading = self.heading?;
+                (
+                    orbit::roll_target(
+                        &center,
+                        radius,
+                        direction,
+                        &position,
+                        current_heading,
+                        self.airspeed.unwrap_or(self.default_airspeed),
+                        bank_limit,
+                    ),
+                    self.altitude_pitch_target(altitude, dt)?,
+                )
+            }
+            AutopilotMode::Holding {
+                fix,
+                inbound_course,
+                leg,
+                direction,
+                bank_limit,
+                altitude,
+            } => {
+                let position = self.position.clone()?;
+                let current_heading = self.heading?;
+                let holding = self.holding.get_or_insert_with(|| {
+                    Holding::new(position.clone(), fix, inbound_course, leg, direction)
+                });
+                let roll = holding.roll_target(
+                    &position,
+                    current_heading,
+                    self.airspeed.unwrap_or(self.default_airspeed),
+                    bank_limit,
+                );
+
+                (roll, self.altitude_pitch_target(altitude, dt)?)
+            }
+            AutopilotMode::Glide { bank_limit } => {
+                let position = self.position.clone()?;
+                let current_heading = self.heading?;
+                let airspeed = self.airspeed?;
+                let altitude = self.climb_rate.altitude()?;
+
+                let glide = self
+                    .glide
+                    .get_or_insert_with(|| Glide::new(self.pitch.law.setpoint()));
+                if let Some(status) = glide.update(
+                    &self.airports,
+                    &position,
+                    altitude,
+                    self.terrain_elevation,
+                    self.glide_ratio,
+                    dt,
+                ) {
+                    _ = tx.send(ServerBoundMessage::Broadcast(
+                        ClientBoundMessage::GlideStatus(status),
+                    ));
+                }
+
+                // Wings level with nowhere to go
+                let roll = glide.track(&self.airports, &position).map_or(0.0, |track| {
+                    heading::roll_target(track, current_heading, bank_limit)
+                });
+
+                (roll, glide.pitch_target(self.glide_speed, airspeed, dt))
+            }
+            AutopilotMode::TerrainFollow {
+                heading,
+                bank_limit,
+                clearance,
+            } => {
+                let current_heading = self.heading?;
+                let altitude = self.climb_rate.altitude()?;
+                let below = self.terrain_elevation?;
+
+                let terrain_follow = self
+                    .terrain_follow
+                    .get_or_insert_with(|| TerrainFollow::new(clearance));
+                if let Some(status) =
+                    terrain_follow.update(altitude, below, &self.terrain_ahead, dt)
+                {
+                    _ = tx.send(ServerBoundMessage::Broadcast(
+                        ClientBoundMessage::TerrainStatus(status),
+                    ));
+                }
+
+                // The ground is taken to be covered at the airspeed
+                let climb_rate = terrain_follow.climb_rate(
+                    altitude,
+                    below,
+                    &self.terrain_ahead,
+                    self.airspeed.unwrap_or(self.default_airspeed),
+                );
+
+                (
+                    heading::roll_target(heading, current_heading, bank_limit),
+                    self.vertical_speed_pitch_target(climb_rate, dt)?,
+                )
+            }
+            AutopilotMode::Approach {
+                heading,
+                course,
+                bank_limit,
+                altitude,
+                flare_height,
+            } => {
+                let approach_speed = self.approach_speed;
+                self.approach
+                    .get_or_insert_with(|| Approach::new(course, flare_height, approach_speed));
+                return self.approach_guidance(heading, bank_limit, altitude, dt, tx);
+            }
+            AutopilotMode::VerticalSpeed { fpm, altitude } => match altitude {
+                Some(altitude) => (
+                    0.0,
+                    self.altitude_capture_pitch_target(altitude, Some(fpm), dt)?,
+                ),
+                None => (0.0, self.vertical_speed_pitch_target(fpm, dt)?),
+            },
+            AutopilotMode::Takeoff {
+                heading,
+                rotate_speed,
+                rotate_pitch,
+                altitude,
+            } => {
+                return self.takeoff_guidance(
+                    heading,
+                    rotate_speed,
+                    rotate_pitch,
+                    altitude,
+                    dt,
+                    tx,
+                );
+            }
+            AutopilotMode::GoAround { heading, altitude } => {
+                return self.go_around_guidance(heading, altitude, dt, tx);
+            }
+        };
+
+        Some(Guidance::attitude(roll, pitch))
+    }
+
+    fn approach_guidance(
+        &mut self,
+        heading: f64,
+        bank_limit: f64,
+        altitude: f64,
+        dt: f64,
+        tx: &crossbeam_channel::Sender<ServerBoundMessage>,
+    ) -> Option<Guidance> {
+        let current_heading = self.heading?;
+        let speed = self.airspeed.unwrap_or(self.default_airspeed);
+        let height = self
+            .climb_rate
+            .altitude()
+            .zip(self.terrain_elevation)
+            .map(|(altitude, elevation)| altitude - elevation);
+
+        let approach = self.approach.as_mut()?;
+
+        let Some(nav) = self.nav1 else {
+            let roll = heading::roll_target(heading, current_heading, bank_limit);
+            return Some(Guidance::attitude(
+                roll,
+                self.altitude_pitch_target(altitude, dt)?,
+            ));
+        };
+
+        approach.update(&nav, current_heading);
+        if let Some(height) = height {
+            approach.update_landing(height, self.on_ground, self.throttle.unwrap_or(0.0));
+
+            if approach.is_unstable(
+                &nav,
+                height,
+                self.on_ground,
+                self.airspeed,
+                self.climb_rate.climb_rate(),
+                dt,
+            ) {
+                // Missed approach straight down the localizer course, back up to the
+                // intercept altitude
+                let mode = AutopilotMode::GoAround {
+                    heading: Some(approach.course()),
+                    altitude,
+                };
+
+                self.set_mode(mode, tx);
+                broadcast_alert(tx, AutopilotAlert::UnstableApproach);
+                return self.guidance(dt, tx);
+            }
+        }
+
+        let course = approach.course();
+        let track = approach.track(&nav);
+        let mut climb_rate = approach.climb_rate(&nav, speed);
+        let mut guidance = Guidance::attitude(0.0, 0.0);
+
+        if let Some(landing) = approach.landing_mut() {
+            if self
+                .airspeed
+                .is_some_and(|airspeed| landing.is_complete(airspeed))
+            {
+                self.disengage(tx);
+                return None;
+            }
+
+            guidance.throttle = landing.throttle(dt);
+            guidance.rudder = landing.rudder(&nav, course, current_heading);
+            if landing.phase() == LandingPhase::Rollout {
+                // Wings level and nose on the runway
+                return Some(guidance);
+            }
+
+            if let Some(height) = height {
+                climb_rate = landing.climb_rate(height).or(climb_rate);
+            }
+        }
+
+        guidance.roll = heading::roll_target(track.unwrap_or(heading), current_heading, bank_limit);
+        guidance.pitch = match climb_rate {
+            Some(climb_rate) => self.vertical_speed_pitch_target(climb_rate, dt)?,
+            None => self.altitude_pitch_target(altitude, dt)?,
+        };
+
+        Some(guidance)
+    }
+
+    fn takeoff_guidance(
+        &mut self,
+        heading: f64,
+        rotate_speed: f64,
+        rotate_pitch: f64,
+        altitude: f64,
+        dt: f64,
+        tx: &crossbeam_channel::Sender<ServerBoundMessage>,
+    ) -> Option<Guidance> {
+        let current_heading = self.heading?;
+        let current_altitude = self.climb_rate.altitude()?;
+        let airspeed = self.airspeed?;
+
+        let takeoff = self.takeoff.get_or_insert_with(|| {
+            Takeoff::new(heading, rotate_speed, rotate_pitch, current_altitude)
+        });
+        takeoff.update(airspeed, self.on_ground);
+
+        if takeoff.is_complete(current_altitude) {
+            let mode = AutopilotMode::Heading {
+                heading,
+                bank_limit: HANDOFF_BANK_LIMIT,
+                altitude,
+            };
+
+            self.set_mode(mode, tx);
+            return self.guidance(dt, tx);
+        }
+
+        Some(Guidance {
+            roll: takeoff.roll(current_heading),
+            pitch: takeoff.pitch(dt),
+            rudder: takeoff.rudder(current_heading),
+            throttle: Some(takeoff.throttle()),
+            flaps: None,
+        })
+    }
+
+    fn go_around_guidance(
+        &mut self,
+        heading: Option<f64>,
+        altitude: f64,
+        dt: f64,
+        tx: &crossbeam_channel::Sender<ServerBoundMessage>,
+    ) -> Option<Guidance> {
+        let current_heading = self.heading?;
+        let current_altitude = self.climb_rate.altitude()?;
+        let airspeed = self.airspeed?;
+
+        let go_around = self.go_around.get_or_insert_with(|| {
+            GoAround::new(
+                heading.unwrap_or(current_heading),
+                current_altitude,
+                self.flaps,
+                self.go_around_profile.clone(),
+            )
+        });
+        let flaps = go_around.flaps(airspeed, self.climb_rate.climb_rate());
+
+        if go_around.is_complete(current_altitude, altitude) {
+            let mode = AutopilotMode::Heading {
+                heading: go_around.heading(),
+                bank_limit: HANDOFF_BANK_LIMIT,
+                altitude,
+            };
+
+            self.set_mode(mode, tx);
+            return self.guidance(dt, tx);
+        }
+
+        Some(Guidance {
+            roll: go_around.roll(current_heading),
+            pitch: go_around.pitch(),
+            rudder: None,
+            throttle: Some(go_around.throttle()),
+            flaps,
+        })
+    }
+
+    fn axis_mut(&mut self, axis: ControlAxis) -> &mut AxisState {
+        match axis {
+            ControlAxis::Roll => &mut self.roll,
+            ControlAxis::Pitch => &mut self.pitch,
+        }
+    }
+
+    /// Hands one axis over to a relay experiment, about its current target. The autopilot has
+    /// to be engaged and flying, not just showing cues, so the other axis is held meanwhile.
+    fn start_autotune(
+        &mut self,
+        axis: ControlAxis,
+        rule: TuningRule,
+        tx: &crossbeam_channel::Sender<ServerBoundMessage>,
+    ) {
+        self.stop_autotune(tx);
+
+        let status = if self.flies_attitude() && self.axis_mut(axis).start_autotune() {
+            self.autotune = Some((axis, rule));
+            AutotuneStatus::Running { axis, cycles: 0 }
+        } else {
+            AutotuneStatus::Failed {
+                axis,
+                failure: AutotuneFailure::NotEngaged,
+            }
+        };
+
+        _ = tx.send(ServerBoundMessage::Broadcast(
+            ClientBoundMessage::AutotuneStatus(status),
+        ));
+    }
+
+    fn stop_autotune(&mut self, tx: &crossbeam_channel::Sender<ServerBoundMessage>) {
+        if let Some((axis, _)) = self.autotune.take() {
+            self.axis_mut(axis).stop_autotune();
+            _ = tx.send(ServerBoundMessage::Broadcast(
+                ClientBoundMessage::AutotuneStatus(AutotuneStatus::Failed {
+                    axis,
+                    failure: AutotuneFailure::Cancelled,
+                }),
+            ));
+        }
+    }
+
+    /// Reports how the experiment is going, proposing gains once it has a result.
+    fn check_autotune(&mut self, tx: &crossbeam_channel::Sender<ServerBoundMessage>) {
+        let Some((axis, rule)) = self.autotune else {
+            return;
+        };
+
+        let axis_state = self.axis_mut(axis);
+        let status = match axis_state.autotune_outcome() {
+            None => AutotuneStatus::Running {
+                axis,
+                cycles: axis_state.autotune_cycles(),
+            },
+            Some(Ok(result)) => AutotuneStatus::Proposed {
+                axis,
+                ultimate_gain: result.ultimate_gain,
+                ultimate_period: result.ultimate_period,
+                gains: rule.gains(result),
+            },
+            Some(Err(failure)) => AutotuneStatus::Failed { axis, failure },
+        };
+
+        if !matches!(status, AutotuneStatus::Running { .. }) {
+            self.autotune = None;
+        }
+
+        _ = tx.send(ServerBoundMessage::Broadcast(
+            ClientBoundMessage::AutotuneStatus(status),
+        ));
+    }
+
+    /// Switches to the profile selected, or the one for the loaded aircraft, and reports it.
+    fn select_profile(&mut self, tx: &crossbeam_channel::Sender<ServerBoundMessage>) {
+        if self.profiles.is_empty() {
+            return;
+        }
+
+        let index = match &self.profile_selection {
+            ProfileSelection::Named(name) => self
+                .profiles
+                .iter()
+                .position(|profile| &profile.name == name),
+            ProfileSelection::Automatic => self.aircraft.as_ref().and_then(|icao| {
+                self.profiles
+                    .iter()
+                    .position(|profile| profile.matches(icao))
+            }),
+        }
+        .unwrap_or(0);
+
+        if self.profile != Some(index) {
+            let profile = &self.profiles[index];
+            self.roll.set_law(profile.roll.build());
+            self.pitch.set_law(profile.pitch.build());
+            self.envelope.set_limits(profile.limits.clone());
+            self.default_airspeed = profile.speeds.cruise;
+            self.climb_speed = profile.speeds.climb;
+            self.approach_speed = profile.speeds.approach;
+            self.go_around_profile = profile.go_around.clone();
+            self.glide_speed = profile.speeds.glide;
+            self.glide_ratio = profile.glide_ratio;
+            self.profile = Some(index);
+        }
+
+        let profile = &self.profiles[index];
+        _ = tx.send(ServerBoundMessage::Broadcast(
+            ClientBoundMessage::ProfileStatus(ProfileStatus {
+                active: profile.name.clone(),
+                available: self
+                    .profiles
+                    .iter()
+                    .map(|profile| profile.name.clone())
+                    .collect(),
+                automatic: self.profile_selection == ProfileSelection::Automatic,
+                speeds: profile.speeds,
+            }),
+        ));
+    }
+
+    /// Disengages, or reverts to wings level at the current pitch, when the inputs the engaged
+    /// modes fly on stop arriving.
+    fn check_telemetry(&mut self, tx: &crossbeam_channel::Sender<ServerBoundMessage>) {
+        let engaged = self.mode != AutopilotMode::Off || self.recovery.is_some();
+        let autothrottle_engaged = self.autothrottle_mode != AutothrottleMode::Off;
+
+        if self.watchdog.is_disconnected() {
+            if engaged || autothrottle_engaged {
+                self.disengage(tx);
+                broadcast_alert(tx, AutopilotAlert::ConnectionLost);
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        if engaged && self.watchdog.is_stale(TelemetryInput::Attitude, now) {
+            self.disengage(tx);
+            broadcast_alert(tx, AutopilotAlert::TelemetryLost(TelemetryInput::Attitude));
+            return;
+        }
+
+        if autothrottle_engaged && self.watchdog.is_stale(TelemetryInput::Airspeed, now) {
+            self.disengage_autothrottle(tx);
+            broadcast_alert(tx, AutopilotAlert::TelemetryLost(TelemetryInput::Airspeed));
+        }
+
+        let required: &[TelemetryInput] = match self.mode {
+            AutopilotMode::Off | AutopilotMode::Hold { .. } => &[],
+            AutopilotMode::AltitudeHold { .. }
+            | AutopilotMode::Heading { .. }
+            | AutopilotMode::Lnav { .. }
+            | AutopilotMode::Orbit { .. }
+            | AutopilotMode::Holding { .. }
+            | AutopilotMode::VerticalSpeed { .. } => &[TelemetryInput::Position],
+            // Landing, the flare is judged on the height over the terrain
+            AutopilotMode::Approach {
+                flare_height: Some(_),
+                ..
+            } => &[
+                TelemetryInput::Position,
+                TelemetryInput::Nav1,
+                TelemetryInput::Airspeed,
+                TelemetryInput::Terrain,
+            ],
+            AutopilotMode::Approach { .. } => &[
+                TelemetryInput::Position,
+                TelemetryInput::Nav1,
+                TelemetryInput::Airspeed,
+            ],
+            AutopilotMode::Takeoff { .. }
+            | AutopilotMode::Glide { .. }
+            | AutopilotMode::GoAround { .. } => {
+                &[TelemetryInput::Position, TelemetryInput::Airspeed]
+            }
+            AutopilotMode::TerrainFollow { .. } => &[
+                TelemetryInput::Position,
+                TelemetryInput::Terrain,
+                TelemetryInput::TerrainAhead,
+            ],
+        };
+
+        if let Some(input) = required
+            .iter()
+            .find(|input| self.watchdog.is_stale(**input, now))
+        {
+            let mode = AutopilotMode::Hold {
+                roll: 0.0,
+                pitch: self.pitch.law.setpoint(),
+            };
+
+            self.set_mode(mode, tx);
+            broadcast_alert(tx, AutopilotAlert::Degraded(*input));
+        }
+    }
+
+    /// With the protection on, takes over from whatever is flying on an extreme attitude or a
+    /// stall warning. The warning is expected on the ground and in the landing flare, so it is
+    /// not acted on there.
+    fn check_upset(&mut self, tx: &crossbeam_channel::Sender<ServerBoundMessage>) {
+        if !self.upset_protection || self.recovery.is_some() || self.on_ground {
+            return;
+        }
+
+        let (Some(roll), Some(pitch)) = (self.roll_angle, self.pitch_angle) else {
+            return;
+        };
+
+        let landing = self
+            .approach
+            .as_ref()
+            .and_then(|approach| approach.status().landing)
+            .is_some_and(|phase| phase != LandingPhase::Armed);
+        if let Some(upset) = Upset::detect(roll, pitch, self.stall_warning && !landing) {
+            if self.start_recovery(upset, tx) {
+                broadcast_alert(tx, AutopilotAlert::Upset(upset));
+            }
+        }
+    }
+
+    /// Starts recovering, as long as the attitude and load factor it flies on are known and
+    /// the aircraft is in the air.
+    fn start_recovery(
+        &mut self,
+        upset: Upset,
+        tx: &crossbeam_channel::Sender<ServerBoundMessage>,
+    ) -> bool {
+        if self.on_ground
+            || self.roll_angle.is_none()
+            || self.pitch_angle.is_none()
+            || self.load_factor.is_none()
+        {
+            return false;
+        }
+
+        self.stop_autotune(tx);
+        self.recovery = Some(Recovery::new(
+            upset,
+            self.control_positions
+                .map_or(0.0, |controls| controls.elevator),
+            self.climb_speed,
+            self.default_airspeed,
+            self.envelope.limits().max_load_factor,
+        ));
+        true
+    }
+
+    /// Controls for the recovery under way, if there is one. Once it is complete the aircraft
+    /// is handed to a wings level hold at the attitude recovered to, whatever mode got it
+    /// into trouble.
+    fn recovery_controls(
+        &mut self,
+        dt: f64,
+        tx: &crossbeam_channel::Sender<ServerBoundMessage>,
+    ) -> Option<RecoveryControls> {
+        let recovery = self.recovery.as_mut()?;
+        let roll = self.roll_angle?;
+        let pitch = self.pitch_angle?;
+        let controls = recovery.update(
+            roll,
+            pitch,
+            self.load_factor?,
+            self.stall_warning,
+            self.airspeed,
+            dt,
+        );
+
+        if !recovery.is_complete() {
+            return Some(controls);
+        }
+
+        self.recovery = None;
+        self.set_mode(AutopilotMode::Hold { roll: 0.0, pitch }, tx);
+        self.engage();
+        None
+    }
+
+    /// Switches mode, dropping whatever the last one was keeping track of. The autopilot's
+    /// own switches, such as a handoff after a takeoff, are not broadcast: the clients follow
+    /// them through the status, and a mode engaged over the bus would be picked up afresh,
+    /// restarting it partway through.
+    fn set_mode(
+        &mut self,
+        mode: AutopilotMode,
+        tx: &crossbeam_channel::Sender<ServerBoundMessage>,
+    ) {
+        self.stop_autotune(tx);
+        self.mode = mode;
+        self.altitude_capture = None;
+        self.lnav = None;
+        self.holding = None;
+        self.glide = None;
+        self.approach = None;
+        self.takeoff = None;
+        self.go_around = None;
+        self.terrain_follow = None;
+
+        // Terrain ahead is only probed while following it, so any left is out of date
+        self.terrain_ahead.clear();
+        self.watchdog.forget(TelemetryInput::TerrainAhead);
+    }
+
+    fn disengage(&mut self, tx: &crossbeam_channel::Sender<ServerBoundMessage>) {
+        self.disengage_autopilot(tx);
+        self.disengage_autothrottle(tx);
+    }
+
+    fn disengage_autopilot(&mut self, tx: &crossbeam_channel::Sender<ServerBoundMessage>) {
+        self.recovery = None;
+        self.set_mode(AutopilotMode::Off, tx);
+    }
+
+    fn disengage_autothrottle(&mut self, tx: &crossbeam_channel::Sender<ServerBoundMessage>) {
+        self.autothrottle_mode = AutothrottleMode::Off;
+        _ = tx.send(ServerBoundMessage::Broadcast(
+            ClientBoundMessage::SetAutothrottleMode(AutothrottleMode::Off),
+        ));
+    }
+
+    /// Flight mode annunciation of what each channel is flying and what it has armed.
+    fn status(&self) -> AutopilotStatus {
+        let mut status = AutopilotStatus {
+            mode: Some(self.mode.clone()).filter(|mode| *mode != AutopilotMode::Off),
+            thrust: match self.autothrottle_mode {
+                AutothrottleMode::Off => None,
+                AutothrottleMode::Speed { .. } => Some(ThrustMode::Speed),
+            },
+            protection: self.envelope.protection(),
+            flight_director: self.flight_director,
+            upset_protection: self.upset_protection,
+            recovery: self.recovery.as_ref().map(|recovery| RecoveryStatus {
+                upset: recovery.upset(),
+                phase: recovery.phase(),
+            }),
+            ..Default::default()
+        };
+
+        // Until the first cycle with the mode engaged, a capture starts out flying the rate
+        let (altitude_mode, altitude_armed) = self
+            .altitude_capture
+            .as_ref()
+            .map(|capture| (capture.mode(), capture.armed()))
+            .unwrap_or((
+                VerticalMode::VerticalSpeed,
+                Some(VerticalMode::AltitudeHold),
+            ));
+
+        match &self.mode {
+            AutopilotMode::Off => {}
+            AutopilotMode::Hold { .. } => {
+                status.lateral = Some(LateralMode::Roll);
+                status.vertical = Some(VerticalMode::Pitch);
+            }
+            AutopilotMode::AltitudeHold { .. } => {
+                status.lateral = Some(LateralMode::Roll);
+                status.vertical = Some(altitude_mode);
+                status.vertical_armed = altitude_armed;
+            }
+            AutopilotMode::Heading { .. } => {
+                status.lateral = Some(LateralMode::Heading);
+                status.vertical = Some(altitude_mode);
+                status.vertical_armed = altitude_armed;
+            }
+            AutopilotMode::Lnav { .. } => {
+                status.lateral = Some(LateralMode::Lnav);
+                status.vertical = Some(altitude_mode);
+                status.vertical_armed = altitude_armed;
+            }
+            AutopilotMode::Orbit { .. } => {
+                status.lateral = Some(LateralMode::Orbit);
+                status.vertical = Some(altitude_mode);
+                status.vertical_armed = altitude_armed;
+            }
+            AutopilotMode::Holding { .. } => {
+                status.lateral = Some(LateralMode::Hold);
+                status.vertical = Some(altitude_mode);
+                status.vertical_armed = altitude_armed;
+            }
+            AutopilotMode::Glide { .. } => {
+                status.lateral = Some(LateralMode::Direct);
+                status.vertical = Some(VerticalMode::Glide);
+            }
+            AutopilotMode::TerrainFollow { .. } => {
+                status.lateral = Some(LateralMode::Heading);
+                status.vertical = Some(VerticalMode::TerrainFollow);
+            }
+            AutopilotMode::VerticalSpeed { altitude, .. } => {
+                status.lateral = Some(LateralMode::Roll);
+                if altitude.is_some() {
+                    status.vertical = Some(altitude_mode);
+                    status.vertical_armed = altitude_armed;
+                } else {
+                    status.vertical = Some(VerticalMode::VerticalSpeed);
+                }
+            }
+            AutopilotMode::Approach { .. } => {
+                let approach = self.approach.as_ref().map(|approach| approach.status());
+
+                if approach.is_some_and(|approach| approach.localizer == CaptureState::Captured) {
+                    status.lateral = Some(LateralMode::Localizer);
+                } else {
+                    status.lateral = Some(LateralMode::Heading);
+                    status.lateral_armed = Some(LateralMode::Localizer);
+                }
+
+                if approach.is_some_and(|approach| approach.glideslope == CaptureState::Captured) {
+                    status.vertical = Some(VerticalMode::Glideslope);
+                } else {
+                    status.vertical = Some(altitude_mode);
+                    status.vertical_armed = Some(VerticalMode::Glideslope);
+                }
+
+                match approach.and_then(|approach| approach.landing) {
+                    Some(LandingPhase::Armed) if status.vertical_armed.is_none() => {
+                        status.vertical_armed = Some(VerticalMode::Flare);
+                    }
+                    Some(LandingPhase::Flare) => {
+                        status.vertical = Some(VerticalMode::Flare);
+                        status.thrust = Some(ThrustMode::Retard);
+                    }
+                    Some(LandingPhase::Rollout) => {
+                        status.lateral = Some(LateralMode::Rollout);
+                        status.vertical = Some(VerticalMode::Flare);
+                        status.thrust = Some(ThrustMode::Retard);
+                    }
+                    _ => {}
+                }
+            }
+            AutopilotMode::Takeoff { .. } => {
+                status.thrust = Some(ThrustMode::Takeoff);
+                status.vertical = Some(VerticalMode::Takeoff);
+                status.vertical_armed = Some(VerticalMode::AltitudeHold);
+                status.lateral = Some(LateralMode::Runway);
+                status.lateral_armed = Some(LateralMode::Heading);
+            }
+            AutopilotMode::GoAround { .. } => {
+                status.thrust = Some(ThrustMode::GoAround);
+                status.vertical = Some(VerticalMode::GoAround);
+                status.vertical_armed = Some(VerticalMode::AltitudeHold);
+                status.lateral = Some(LateralMode::Heading);
+            }
+        }
+
+        status
+    }
+
+    fn yaw_damper_rudder(&mut self, dt: f64) -> Option<f64> {
+        if self.on_ground {
+            return None;
+        }
+
+        let sideslip = self.sideslip?;
+        Some(self.yaw_damper.rudder(sideslip, dt))
+    }
+
+    fn throttle_target(&mut self, dt: f64) -> Option<f64> {
+        match self.autothrottle_mode {
+            AutothrottleMode::Off => None,
+            AutothrottleMode::Speed { knots } => {
+                let airspeed = self.airspeed?;
+                Some(self.autothrottle.throttle(knots, airspeed, dt))
+            }
+        }
+    }
+
+    fn altitude_pitch_target(&mut self, altitude: f64, dt: f64) -> Option<f64> {
+        self.altitude_capture_pitch_target(altitude, None, dt)
+    }
+
+    fn altitude_capture_pitch_target(
+        &mut self,
+        altitude: f64,
+        rate: Option<f64>,
+        dt: f64,
+    ) -> Option<f64> {
+        let current_altitude = self.climb_rate.altitude()?;
+        if self
+            .altitude_capture
+            .as_ref()
+            .map(|capture| capture.target())
+            != Some(altitude)
+        {
+            self.altitude_capture = Some(AltitudeCapture::new(altitude, rate));
+        }
+
+        let climb_rate = self.altitude_capture.as_mut()?.climb_rate(current_altitude);
+        self.vertical_speed_pitch_target(climb_rate, dt)
+    }
+
+    fn vertical_speed_pitch_target(&mut self, climb_rate: f64, dt: f64) -> Option<f64> {
+        self.climb_rate.altitude()?;
+        Some(
+            self.vertical_speed_hold
+                .pitch_target(climb_rate, self.climb_rate.climb_rate(), dt),
+        )
+    }
+}
+
+/// What the active mode wants from the controls this cycle: an attitude for the roll and
+/// pitch loops, plus any surfaces it drives directly.
+struct Guidance {
+    roll: f64,
+    pitch: f64,
+    rudder: Option<f64>,
+    throttle: Option<f64>,
+    flaps: Option<f64>,
+}
+
+impl Guidance {
+    fn attitude(roll: f64, pitch: f64) -> Self {
+        Self {
+            roll,
+            pitch,
+            rudder: None,
+            throttle: None,
+            flaps: None,
+        }
+    }
+}
+
+fn broadcast_alert(tx: &crossbeam_channel::Sender<ServerBoundMessage>, alert: AutopilotAlert) {
+    _ = tx.send(ServerBoundMessage::Broadcast(
+        ClientBoundMessage::AutopilotAlert(alert),
+    ));
+}
+
+/// Surface positions last read back from the sim, as fractions of full travel.
+#[derive(Debug, Clone, Copy)]
+struct ControlPositions {
+    aileron: f64,
+    elevator: f64,
+    rudder: f64,
+}
+
+pub struct AxisState {
+    law: Box<dyn ControlLaw>,
+    relay: Option<RelayExperiment>,
+    clock: SampleClock,
+    value: Option<f64>,
+    trim: f64,
+}
+
+impl AxisState {
+    pub fn new(law: Box<dyn ControlLaw>) -> Self {
+        Self {
+            law,
+            relay: None,
+            clock: SampleClock::new(),
+            value: None,
+            trim: 0.0,
+        }
+    }
+
+    /// Restarts the loop holding the given measurement with the control where it is, so the
+    /// output does not jump.
+    pub fn engage(&mut self, measurement: Option<f64>, control: Option<f64>) {
+        self.law.reset();
+        self.trim = control.unwrap_or(0.0);
+        self.value = control;
+        if let Some(measurement) = measurement {
+            self.law.set_setpoint(measurement);
+        }
+    }
+
+    /// What went into the last update, given the measurement it was made with.
+    pub fn internals(&self, axis: ControlAxis, measurement: f64) -> Option<ControllerInternals> {
+        Some(ControllerInternals {
+            axis,
+            setpoint: self.law.setpoint(),
+            measurement,
+            output: self.finite_value()?,
+            terms: self.law.terms(),
+            gains: self.law.gains(),
+        })
+    }
+
+    /// Swaps in another law, carrying over the setpoint and the current output as trim so
+    /// the switch does not jump the controls.
+    pub fn set_law(&mut self, mut law: Box<dyn ControlLaw>) {
+        law.set_setpoint(self.law.setpoint());
+        self.law = law;
+        self.trim = self.value.unwrap_or(self.trim);
+    }
+
+    /// Moves the setpoint toward the target by no more than rate (per second) over dt seconds.
+    pub fn ramp_setpoint(&mut self, target: f64, rate: f64, dt: f64) {
+        let step = rate * dt;
+        let setpoint = self.law.setpoint();
+        self.law
+            .set_setpoint(setpoint + (target - setpoint).clamp(-step, step));
+    }
+
+    pub fn finite_value(&self) -> Option<f64> {
+        self.value
+            .and_then(|value| if value.is_finite() { Some(value) } else { None })
+    }
+
+    /// Sim time (seconds) is the latest known, if any, and now when the measurement arrived.
+    pub fn update(&mut self, measurement: f64, sim_time: Option<f64>, now: Instant) {
+        let dt = self.clock.sample(sim_time, now);
+        self.value = Some(match &mut self.relay {
+            Some(relay) => relay.update(measurement, dt.unwrap_or(0.0)),
+            None => self.trim + self.law.update(measurement, dt),
+        });
+    }
+
+    /// Starts a relay experiment about the current setpoint and output, if there is one.
+    pub fn start_autotune(&mut self) -> bool {
+        let Some(value) = self.finite_value() else {
+            return false;
+        };
+
+        self.relay = Some(RelayExperiment::new(self.law.setpoint(), value));
+        true
+    }
+
+    /// Hands the axis back to its law, trimmed where the relay was centred.
+    pub fn stop_autotune(&mut self) {
+        if let Some(relay) = self.relay.take() {
+            self.law.reset();
+            self.trim = relay.trim();
+        }
+    }
+
+    pub fn autotune_cycles(&self) -> usize {
+        self.relay.as_ref().map_or(0, |relay| relay.cycles())
+    }
+
+    /// The outcome of the experiment once there is one, at which point it is stopped.
+    pub fn autotune_outcome(&mut self) -> Option<Result<RelayResult, AutotuneFailure>> {
+        let outcome = self.relay.as_ref()?.outcome()?;
+        self.stop_autotune();
+        Some(outcome)
+    }
+}
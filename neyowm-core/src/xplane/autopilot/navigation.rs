@@ -0,0 +1,215 @@
+use crate::Waypoint;
+
+use super::heading::heading_error;
+
+pub(crate) const EARTH_RADIUS_NM: f64 = 3440.065;
+
+pub(crate) const FEET_PER_NM: f64 = 6076.12;
+
+const GRAVITY_KNOTS_PER_SECOND: f64 = 19.06;
+
+/// Intercept angle (degrees) commanded per nautical mile of cross-track error.
+const CROSS_TRACK_GAIN: f64 = 30.0;
+
+/// Steepest angle (degrees) at which a leg is intercepted.
+const MAX_INTERCEPT_ANGLE: f64 = 45.0;
+
+/// Bank angle (degrees) assumed when working out how early to start a turn onto the next leg.
+const ANTICIPATION_BANK_ANGLE: f64 = 15.0;
+
+/// Great-circle initial bearing in degrees true from one point to another.
+pub fn bearing(from: &Waypoint, to: &Waypoint) -> f64 {
+    let (lat1, lon1) = (from.latitude.to_radians(), from.longitude.to_radians());
+    let (lat2, lon2) = (to.latitude.to_radians(), to.longitude.to_radians());
+    let dlon = lon2 - lon1;
+
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Great-circle distance in nautical miles between two points.
+pub fn distance(from: &Waypoint, to: &Waypoint) -> f64 {
+    angular_distance(from, to) * EARTH_RADIUS_NM
+}
+
+/// Point reached by following a great circle from a point, setting off on the given bearing
+/// (degrees true), for the given distance (nautical miles).
+pub fn destination(from: &Waypoint, bearing: f64, distance: f64) -> Waypoint {
+    let (lat1, lon1) = (from.latitude.to_radians(), from.longitude.to_radians());
+    let bearing = bearing.to_radians();
+    let angle = distance / EARTH_RADIUS_NM;
+
+    let lat2 = (lat1.sin() * angle.cos() + lat1.cos() * angle.sin() * bearing.cos()).asin();
+    let lon2 = lon1
+        + (bearing.sin() * angle.sin() * lat1.cos()).atan2(angle.cos() - lat1.sin() * lat2.sin());
+
+    Waypoint {
+        latitude: lat2.to_degrees(),
+        longitude: (lon2.to_degrees() + 540.0).rem_euclid(360.0) - 180.0,
+    }
+}
+
+fn angular_distance(from: &Waypoint, to: &Waypoint) -> f64 {
+    let (lat1, lat2) = (from.latitude.to_radians(), to.latitude.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (to.longitude - from.longitude).to_radians();
+
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * a.sqrt().atan2((1.0 - a).sqrt())
+}
+
+/// Where the aircraft sits relative to the great circle through a leg.
+#[derive(Debug, Clone, Copy)]
+pub struct LegGeometry {
+    /// Course of the leg abeam the aircraft, degrees true.
+    pub desired_track: f64,
+    /// Distance off the leg in nautical miles, positive right of course.
+    pub cross_track: f64,
+    /// Distance left to fly to the end of the leg, measured along it, in nautical miles.
+    pub distance_to_go: f64,
+}
+
+impl LegGeometry {
+    pub fn new(from: &Waypoint, to: &Waypoint, position: &Waypoint) -> Self {
+        let leg_course = bearing(from, to);
+        let bearing_to_position = bearing(from, position);
+        let angle_to_position = angular_distance(from, position);
+
+        let cross_track = (angle_to_position.sin()
+            * (bearing_to_position - leg_course).to_radians().sin())
+        .asin();
+        let along_track = (angle_to_position.cos() / cross_track.cos())
+            .clamp(-1.0, 1.0)
+            .acos()
+            .copysign(
+                heading_error(bearing_to_position, leg_course)
+                    .to_radians()
+                    .cos(),
+            );
+
+        let angle_to_go = angular_distance(position, to);
+        let desired_track = if angle_to_go.sin().abs() > f64::EPSILON {
+            // The bearing straight to the end of the leg is rotated toward the leg by the angle
+            // the cross-track distance subtends from here
+            let correction = (cross_track.sin() / angle_to_go.sin())
+                .clamp(-1.0, 1.0)
+                .asin();
+            (bearing(position, to) + correction.to_degrees()).rem_euclid(360.0)
+        } else {
+            leg_course
+        };
+
+        Self {
+            desired_track,
+            cross_track: cross_track * EARTH_RADIUS_NM,
+            distance_to_go: (angular_distance(from, to) - along_track) * EARTH_RADIUS_NM,
+        }
+    }
+
+    /// Track to fly to converge on the leg, steeper the further off it the aircraft is.
+    pub fn intercept_track(&self) -> f64 {
+        let intercept =
+            (CROSS_TRACK_GAIN * self.cross_track).clamp(-MAX_INTERCEPT_ANGLE, MAX_INTERCEPT_ANGLE);
+        (self.desired_track - intercept).rem_euclid(360.0)
+    }
+}
+
+/// Follows a route leg by leg, starting from wherever the aircraft was when it was engaged.
+pub struct Lnav {
+    from: Waypoint,
+    waypoints: Vec<Waypoint>,
+    active_waypoint: usize,
+}
+
+impl Lnav {
+    pub fn new(position: Waypoint, waypoints: Vec<Waypoint>) -> Self {
+        Self {
+            from: position,
+            waypoints,
+            active_waypoint: 0,
+        }
+    }
+
+    /// Track to fly from the given position, sequencing to the next leg once the turn onto
+    /// it should begin. Speed is in knots. After the last waypoint the final leg is extended.
+    pub fn desired_track(&mut self, position: &Waypoint, speed: f64) -> Option<f64> {
+        loop {
+            let to = self.waypoints.get(self.active_waypoint)?;
+            let leg = LegGeometry::new(&self.from, to, position);
+
+            let Some(next) = self.waypoints.get(self.active_waypoint + 1) else {
+                return Some(leg.intercept_track());
+            };
+
+            let turn = heading_error(bearing(to, next), leg.desired_track).abs();
+            if leg.distance_to_go > turn_anticipation(speed, turn) {
+                return Some(leg.intercept_track());
+            }
+
+            self.from = to.clone();
+            self.active_waypoint += 1;
+        }
+    }
+}
+
+/// Distance (nautical miles) before a waypoint at which to start a turn of the given number
+/// of degrees so the turn finishes on the next leg.
+pub fn turn_anticipation(speed: f64, turn: f64) -> f64 {
+    turn_radius(speed, ANTICIPATION_BANK_ANGLE) * (turn.min(150.0) / 2.0).to_radians().tan()
+}
+
+/// Radius (nautical miles) of a level turn at the given speed (knots) and bank (degrees).
+pub fn turn_radius(speed: f64, bank: f64) -> f64 {
+    speed.powi(2) / (GRAVITY_KNOTS_PER_SECOND * bank.to_radians().tan()) / 3600.0
+}
+
+/// Bank (degrees) for a level turn at the given speed (knots) and radius (nautical miles).
+pub fn turn_bank(speed: f64, radius: f64) -> f64 {
+    (speed.powi(2) / (GRAVITY_KNOTS_PER_SECOND * radius * 3600.0))
+        .atan()
+        .to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waypoint(latitude: f64, longitude: f64) -> Waypoint {
+        Waypoint {
+            latitude,
+            longitude,
+        }
+    }
+
+    #[test]
+    fn test_leg_geometry() {
+        let from = waypoint(0.0, 0.0);
+        let to = waypoint(0.0, 1.0);
+
+        let leg = LegGeometry::new(&from, &to, &waypoint(-0.1, 0.5));
+        assert!((leg.desired_track - 90.0).abs() < 0.1);
+        assert!((leg.cross_track - 6.0).abs() < 0.1);
+        assert!((leg.distance_to_go - 30.0).abs() < 0.1);
+        assert!(leg.intercept_track() < 90.0);
+    }
+
+    #[test]
+    fn test_sequencing_with_anticipation() {
+        let mut lnav = Lnav::new(
+            waypoint(0.0, 0.0),
+            vec![waypoint(0.0, 1.0), waypoint(1.0, 1.0)],
+        );
+
+        let speed = 120.0;
+        let lead = turn_anticipation(speed, 90.0);
+        let position = waypoint(0.0, 1.0 - lead * 1.5 / 60.0);
+        lnav.desired_track(&position, speed);
+        assert_eq!(lnav.active_waypoint, 0);
+
+        let position = waypoint(0.0, 1.0 - lead * 0.5 / 60.0);
+        let track = lnav.desired_track(&position, speed).unwrap();
+        assert_eq!(lnav.active_waypoint, 1);
+        assert!(heading_error(track, 0.0).abs() < 45.0);
+    }
+}
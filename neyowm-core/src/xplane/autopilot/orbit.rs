@@ -0,0 +1,75 @@
+use crate::{TurnDirection, Waypoint};
+
+use super::{
+    heading::{self, turn_sign, MAX_BANK_LIMIT},
+    navigation::{bearing, distance, turn_bank},
+};
+
+/// Degrees the track is turned in toward the circle per nautical mile outside it.
+const RADIUS_GAIN: f64 = 30.0;
+
+/// Largest correction (degrees) off the tangent, which points straight at the center from
+/// outside and straight away from it inside.
+const MAX_CORRECTION: f64 = 90.0;
+
+/// Roll target for circling the center at the radius (nautical miles) in the given direction.
+/// Speed is in knots.
+pub fn roll_target(
+    center: &Waypoint,
+    radius: f64,
+    direction: TurnDirection,
+    position: &Waypoint,
+    heading: f64,
+    speed: f64,
+    bank_limit: f64,
+) -> f64 {
+    let sign = turn_sign(direction);
+    let bank_limit = bank_limit.abs().min(MAX_BANK_LIMIT);
+
+    let tangent = bearing(center, position) + sign * 90.0;
+    let correction = (RADIUS_GAIN * (distance(center, position) - radius))
+        .clamp(-MAX_CORRECTION, MAX_CORRECTION);
+    let track = (tangent + sign * correction).rem_euclid(360.0);
+
+    // Bank the circle takes at this speed, so the heading does not have to lag the tangent to
+    // keep the turn going. Faded out away from the circle, where the track is not turning.
+    let bank =
+        turn_bank(speed, radius.max(f64::EPSILON)) * (1.0 - correction.abs() / MAX_CORRECTION);
+
+    (heading::roll_target(track, heading, bank_limit) + sign * bank).clamp(-bank_limit, bank_limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xplane::autopilot::navigation::destination;
+
+    #[test]
+    fn test_orbit_settles_on_radius() {
+        let center = Waypoint {
+            latitude: 45.0,
+            longitude: -73.0,
+        };
+        let speed = 100.0;
+        let dt = 0.5;
+
+        // Coordinated turns at the commanded bank, starting well outside the circle
+        let mut position = destination(&center, 270.0, 5.0);
+        let mut heading = 90.0;
+        for _ in 0..2400 {
+            let roll = roll_target(
+                &center,
+                1.5,
+                TurnDirection::Left,
+                &position,
+                heading,
+                speed,
+                25.0,
+            );
+            heading += (19.06 * roll.to_radians().tan() / speed).to_degrees() * dt;
+            position = destination(&position, heading, speed * dt / 3600.0);
+        }
+
+        assert!((distance(&center, &position) - 1.5).abs() < 0.1);
+    }
+}
@@ -0,0 +1,131 @@
+use xplaneconnect::SetControlSurface;
+
+/// Difference (fraction of full travel) between what was written and what is read back above
+/// which someone else is taken to be moving the control. Kept above how far a rate limited
+/// surface can move between a write and the next read.
+const OVERRIDE_THRESHOLD: f64 = 0.3;
+
+/// How long (seconds) the difference has to persist before it counts as an override, so a
+/// read that races a write is not mistaken for one.
+const OVERRIDE_TIME: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overridden {
+    FlightControls,
+    Throttle,
+}
+
+/// Detects a pilot fighting the autopilot by comparing the controls read back from the sim
+/// against the last values written to them.
+pub struct PilotOverride {
+    aileron: ControlMonitor,
+    elevator: ControlMonitor,
+    rudder: ControlMonitor,
+    throttle: ControlMonitor,
+}
+
+impl PilotOverride {
+    pub fn new() -> Self {
+        Self {
+            aileron: ControlMonitor::new(),
+            elevator: ControlMonitor::new(),
+            rudder: ControlMonitor::new(),
+            throttle: ControlMonitor::new(),
+        }
+    }
+
+    /// Records what was just written. Controls left as None are not being driven and are
+    /// not monitored.
+    pub fn commanded(&mut self, controls: &SetControlSurface) {
+        self.aileron.command(controls.aileron);
+        self.elevator.command(controls.elevator);
+        self.rudder.command(controls.rudder);
+        self.throttle.command(controls.throttle);
+    }
+
+    /// Checks the controls read back dt seconds after the last check. Overriding the flight
+    /// controls is reported ahead of overriding the throttle.
+    pub fn update(
+        &mut self,
+        aileron: f64,
+        elevator: f64,
+        rudder: f64,
+        throttle: f64,
+        dt: f64,
+    ) -> Option<Overridden> {
+        let aileron = self.aileron.update(aileron, dt);
+        let elevator = self.elevator.update(elevator, dt);
+        let rudder = self.rudder.update(rudder, dt);
+        let throttle = self.throttle.update(throttle, dt);
+
+        if aileron || elevator || rudder {
+            Some(Overridden::FlightControls)
+        } else if throttle {
+            Some(Overridden::Throttle)
+        } else {
+            None
+        }
+    }
+}
+
+struct ControlMonitor {
+    commanded: Option<f64>,
+    elapsed: f64,
+}
+
+impl ControlMonitor {
+    fn new() -> Self {
+        Self {
+            commanded: None,
+            elapsed: 0.0,
+        }
+    }
+
+    fn command(&mut self, value: Option<f64>) {
+        if value.is_none() {
+            self.elapsed = 0.0;
+        }
+
+        self.commanded = value;
+    }
+
+    fn update(&mut self, measured: f64, dt: f64) -> bool {
+        match self.commanded {
+            Some(commanded) if (commanded - measured).abs() > OVERRIDE_THRESHOLD => {
+                self.elapsed += dt;
+            }
+            _ => self.elapsed = 0.0,
+        }
+
+        self.elapsed >= OVERRIDE_TIME
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sustained_override() {
+        let mut pilot_override = PilotOverride::new();
+        pilot_override.commanded(&SetControlSurface {
+            aileron: Some(0.1),
+            throttle: Some(0.5),
+            ..Default::default()
+        });
+
+        // A brief disagreement, then back in step
+        assert_eq!(pilot_override.update(0.6, 0.0, 0.8, 0.5, 0.5), None);
+        assert_eq!(pilot_override.update(0.1, 0.0, 0.8, 0.5, 0.5), None);
+
+        assert_eq!(pilot_override.update(0.1, 0.0, 0.0, 1.0, 0.5), None);
+        assert_eq!(
+            pilot_override.update(0.1, 0.0, 0.0, 1.0, 0.5),
+            Some(Overridden::Throttle)
+        );
+        assert_eq!(
+            pilot_override.update(-0.5, 0.0, 0.0, 1.0, 1.0),
+            Some(Overridden::FlightControls)
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{RecoveryPhase, Upset};
+
+/// Nose up and nose down pitch (degrees), and bank either way, beyond which the aircraft is
+/// upset. A little past the usual definition, so modes flying at their limits do not set it
+/// off.
+const NOSE_HIGH: f64 = 30.0;
+const NOSE_LOW: f64 = -20.0;
+const STEEP_BANK: f64 = 60.0;
+
+/// Load factor (g) the wing is unloaded to after a stall or with the nose high, taking the
+/// angle of attack off it. Other upsets are only relieved of any pull, down to 1 g.
+const UNLOADED: f64 = 0.5;
+
+/// How far (g) above the load factor aimed for the wing counts as unloaded.
+const UNLOAD_MARGIN: f64 = 0.3;
+
+/// Elevator travel per second, per g of error, to reach the load factor wanted. Slowed down
+/// with the square of the speed above the maximum, as each bit of elevator pulls harder.
+const ELEVATOR_RATE: f64 = 0.3;
+
+/// Aileron travel per degree of bank, and the most that is used.
+const AILERON_GAIN: f64 = 0.02;
+const MAX_AILERON: f64 = 0.6;
+
+/// Bank (degrees) within which the wings count as level.
+const WINGS_LEVEL: f64 = 10.0;
+
+/// Pitch (degrees) the nose is brought back to, and how close to it counts as recovered.
+const RECOVERED_PITCH: f64 = 2.0;
+const PITCH_TOLERANCE: f64 = 5.0;
+
+/// Load factor (g) asked for per degree the nose is below the recovered pitch.
+const PITCH_G_GAIN: f64 = 0.1;
+
+impl Upset {
+    /// The upset the aircraft is in, if it is in one. A stall takes priority over the
+    /// attitude, then pitch over bank.
+    pub fn detect(roll: f64, pitch: f64, stall_warning: bool) -> Option<Self> {
+        if stall_warning {
+            Some(Upset::Stall)
+        } else if pitch > NOSE_HIGH {
+            Some(Upset::NoseHigh)
+        } else if pitch < NOSE_LOW {
+            Some(Upset::NoseLow)
+        } else if roll.abs() > STEEP_BANK {
+            Some(Upset::Bank)
+        } else {
+            None
+        }
+    }
+}
+
+/// Aileron and elevator (fractions of full travel) to write, and the throttle when the
+/// recovery is setting the power.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecoveryControls {
+    pub aileron: f64,
+    pub elevator: f64,
+    pub throttle: Option<f64>,
+}
+
+/// Standard upset recovery, flown on the surfaces directly rather than through the attitude
+/// loops so it does not depend on whatever got the aircraft into trouble. The elevator is
+/// moved for a load factor: unloaded first, 1 g while the wings are rolled level, then as
+/// much as the limit allows to bring the nose back to the horizon.
+pub struct Recovery {
+    upset: Upset,
+    phase: RecoveryPhase,
+    elevator: f64,
+    throttle: Option<f64>,
+    min_speed: f64,
+    max_speed: f64,
+    max_load_factor: f64,
+    complete: bool,
+}
+
+impl Recovery {
+    /// Starts from the elevator where it is. Once level, power is added below the minimum
+    /// speed and taken off above the maximum (knots), and the pull never goes past the load
+    /// factor limit (g).
+    pub fn new(
+        upset: Upset,
+        elevator: f64,
+        min_speed: f64,
+        max_speed: f64,
+        max_load_factor: f64,
+    ) -> Self {
+        Self {
+            upset,
+            phase: RecoveryPhase::Unload,
+            elevator,
+            throttle: None,
+            min_speed,
+            max_speed,
+            max_load_factor,
+            complete: false,
+        }
+    }
+
+    pub fn upset(&self) -> Upset {
+        self.upset
+    }
+
+    pub fn phase(&self) -> RecoveryPhase {
+        self.phase
+    }
+
+    /// Wings level, nose on the horizon, not stalled and back up to speed.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Controls for the attitude (degrees), normal load factor (g) and airspeed (knots) dt
+    /// seconds after the last update, moving on to the next phase as each is done. The wing
+    /// stalling again goes back to unloading it.
+    pub fn update(
+        &mut self,
+        roll: f64,
+        pitch: f64,
+        load_factor: f64,
+        stall_warning: bool,
+        airspeed: Option<f64>,
+        dt: f64,
+    ) -> RecoveryControls {
+        let stalled = stall_warning || matches!(self.upset, Upset::Stall | Upset::NoseHigh);
+        let unloaded = if stalled { UNLOADED } else { 1.0 };
+
+        if stall_warning {
+            self.phase = RecoveryPhase::Unload;
+        }
+
+        match self.phase {
+            RecoveryPhase::Unload
+                if !stall_warning
+                    && load_factor <= unloaded + UNLOAD_MARGIN
+                    && pitch <= NOSE_HIGH =>
+            {
+                self.phase = RecoveryPhase::RollLevel;
+            }
+            RecoveryPhase::RollLevel if roll.abs() < WINGS_LEVEL => {
+                self.phase = RecoveryPhase::Pitch;
+            }
+            RecoveryPhase::Pitch => {
+                self.complete = roll.abs() < WINGS_LEVEL
+                    && (pitch - RECOVERED_PITCH).abs() < PITCH_TOLERANCE
+                    && airspeed.is_none_or(|airspeed| airspeed >= self.min_speed);
+            }
+            _ => {}
+        }
+
+        let target_load_factor = match self.phase {
+            RecoveryPhase::Unload => unloaded,
+            RecoveryPhase::RollLevel => 1.0,
+            RecoveryPhase::Pitch => (1.0 + PITCH_G_GAIN * (RECOVERED_PITCH - pitch))
+                .clamp(UNLOADED, self.max_load_factor),
+        };
+        let rate = ELEVATOR_RATE
+            * airspeed.map_or(1.0, |airspeed| (self.max_speed / airspeed).powi(2).min(1.0));
+        self.elevator =
+            (self.elevator + rate * (target_load_factor - load_factor) * dt).clamp(-1.0, 1.0);
+
+        // Power up to fly out of a stall or a climb, off in a dive, then for the speed
+        self.throttle = match (self.phase, self.upset) {
+            (RecoveryPhase::Pitch, _) => match airspeed {
+                Some(airspeed) if airspeed < self.min_speed => Some(1.0),
+                Some(airspeed) if airspeed > self.max_speed => Some(0.0),
+                _ => self.throttle,
+            },
+            _ if stalled => Some(1.0),
+            (_, Upset::NoseLow) => Some(0.0),
+            _ => self.throttle,
+        };
+
+        RecoveryControls {
+            // No aileron until the wing is unloaded
+            aileron: match self.phase {
+                RecoveryPhase::Unload => 0.0,
+                _ => (-AILERON_GAIN * roll).clamp(-MAX_AILERON, MAX_AILERON),
+            },
+            elevator: self.elevator,
+            throttle: self.throttle,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Upset::detect(20.0, 5.0, false), None);
+        assert_eq!(Upset::detect(20.0, 5.0, true), Some(Upset::Stall));
+        assert_eq!(Upset::detect(0.0, 40.0, false), Some(Upset::NoseHigh));
+        assert_eq!(Upset::detect(90.0, -30.0, false), Some(Upset::NoseLow));
+        assert_eq!(Upset::detect(-120.0, 0.0, false), Some(Upset::Bank));
+    }
+
+    #[test]
+    fn test_stall_recovery_phases() {
+        let mut recovery = Recovery::new(Upset::Stall, 0.2, 75.0, 110.0, 2.0);
+
+        // Pushed forward at full power, wings left alone, until the warning stops
+        let controls = recovery.update(-30.0, 15.0, 1.0, true, Some(45.0), 0.1);
+        assert_eq!(recovery.phase(), RecoveryPhase::Unload);
+        assert!(controls.elevator < 0.2);
+        assert_eq!(controls.aileron, 0.0);
+        assert_eq!(controls.throttle, Some(1.0));
+
+        let controls = recovery.update(-30.0, 0.0, 0.6, false, Some(55.0), 0.1);
+        assert_eq!(recovery.phase(), RecoveryPhase::RollLevel);
+        assert!(controls.aileron > 0.0);
+
+        // Stalling again goes back to unloading
+        recovery.update(-20.0, 0.0, 1.2, true, Some(50.0), 0.1);
+        assert_eq!(recovery.phase(), RecoveryPhase::Unload);
+        recovery.update(-20.0, -5.0, 0.7, false, Some(60.0), 0.1);
+        let level = recovery.update(-5.0, -10.0, 1.0, false, Some(70.0), 0.1);
+        assert_eq!(recovery.phase(), RecoveryPhase::Pitch);
+
+        // Pulled back to the horizon, done once the speed is back
+        let controls = recovery.update(0.0, -10.0, 1.0, false, Some(80.0), 0.1);
+        assert!(controls.elevator > level.elevator);
+        recovery.update(0.0, 1.0, 1.0, false, Some(70.0), 0.1);
+        assert!(!recovery.is_complete());
+        recovery.update(0.0, 1.0, 1.0, false, Some(80.0), 0.1);
+        assert!(recovery.is_complete());
+    }
+
+    #[test]
+    fn test_recovers_from_spiral_dive() {
+        // Point mass with the pitch taken as the flight path, the load factor following the
+        // elevator with the square of the speed, and the aileron setting a roll rate. Speeds
+        // are in knots, so gravity is 19.06 knots per second.
+        let dt = 0.1;
+        let (mut roll, mut pitch, mut speed, mut load_factor) = (120.0_f64, -40.0_f64, 150.0, 2.0);
+        let mut recovery = Recovery::new(Upset::NoseLow, 0.3, 75.0, 110.0, 3.0);
+
+        let mut max_load_factor = load_factor;
+        for _ in 0..600 {
+            let controls = recovery.update(roll, pitch, load_factor, false, Some(speed), dt);
+            if recovery.is_complete() {
+                break;
+            }
+
+            let commanded = 1.0 + 4.0 * controls.elevator * (speed / 100.0).powi(2);
+            load_factor += (commanded - load_factor) * dt / 0.5;
+            max_load_factor = max_load_factor.max(load_factor);
+
+            roll += 60.0 * controls.aileron * dt;
+            let (roll_rad, pitch_rad) = (roll.to_radians(), pitch.to_radians());
+            pitch += (19.06 * (load_factor * roll_rad.cos() - pitch_rad.cos()) / speed)
+                .to_degrees()
+                * dt;
+            let thrust = 4.0 * controls.throttle.unwrap_or(0.5);
+            speed += (thrust - 0.0002 * speed * speed - 19.06 * pitch_rad.sin()) * dt;
+        }
+
+        assert!(recovery.is_complete());
+        // Within a little overshoot of the limit
+        assert!(max_load_factor < 3.0 * 1.1);
+    }
+}
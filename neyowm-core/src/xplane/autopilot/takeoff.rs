@@ -0,0 +1,130 @@
+use super::heading::{self, heading_error};
+
+/// Throttle held from brake release until the climb is handed off.
+const TAKEOFF_THROTTLE: f64 = 1.0;
+
+/// Rudder (fraction of full travel) commanded per degree of heading error on the takeoff roll.
+const RUDDER_HEADING_GAIN: f64 = 0.05;
+
+/// How fast the nose is brought up to the rotation pitch, in degrees per second.
+const ROTATION_RATE: f64 = 3.0;
+
+/// Bank limit (degrees) while holding the runway heading in the initial climb.
+const CLIMB_BANK_LIMIT: f64 = 10.0;
+
+/// Height (feet above the runway) at which the climb is handed off.
+const HANDOFF_HEIGHT: f64 = 400.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TakeoffPhase {
+    Roll,
+    Rotate,
+    Climb,
+}
+
+/// Takeoff roll along the runway heading, rotation at a set speed and the initial climb at
+/// a fixed pitch until clear of the ground.
+pub struct Takeoff {
+    runway_heading: f64,
+    rotate_speed: f64,
+    rotate_pitch: f64,
+    field_elevation: f64,
+    phase: TakeoffPhase,
+    pitch: f64,
+}
+
+impl Takeoff {
+    /// Rotate speed is in knots, rotate pitch in degrees and field elevation in feet.
+    pub fn new(
+        runway_heading: f64,
+        rotate_speed: f64,
+        rotate_pitch: f64,
+        field_elevation: f64,
+    ) -> Self {
+        Self {
+            runway_heading,
+            rotate_speed,
+            rotate_pitch,
+            field_elevation,
+            phase: TakeoffPhase::Roll,
+            pitch: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, airspeed: f64, on_ground: bool) {
+        match self.phase {
+            TakeoffPhase::Roll if airspeed >= self.rotate_speed => {
+                self.phase = TakeoffPhase::Rotate;
+            }
+            TakeoffPhase::Rotate if !on_ground => {
+                self.phase = TakeoffPhase::Climb;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn throttle(&self) -> f64 {
+        TAKEOFF_THROTTLE
+    }
+
+    /// Pitch target, raised smoothly to the rotation pitch once rotate speed is reached.
+    pub fn pitch(&mut self, dt: f64) -> f64 {
+        if self.phase != TakeoffPhase::Roll {
+            self.pitch = (self.pitch + ROTATION_RATE * dt).min(self.rotate_pitch);
+        }
+
+        self.pitch
+    }
+
+    /// Roll target: wings level on the ground, then holding the runway heading.
+    pub fn roll(&self, heading: f64) -> f64 {
+        match self.phase {
+            TakeoffPhase::Roll | TakeoffPhase::Rotate => 0.0,
+            TakeoffPhase::Climb => {
+                heading::roll_target(self.runway_heading, heading, CLIMB_BANK_LIMIT)
+            }
+        }
+    }
+
+    /// Rudder to hold the runway heading while on the ground. Once airborne the rudder is
+    /// left to the yaw damper.
+    pub fn rudder(&self, heading: f64) -> Option<f64> {
+        match self.phase {
+            TakeoffPhase::Roll | TakeoffPhase::Rotate => Some(
+                (RUDDER_HEADING_GAIN * heading_error(self.runway_heading, heading))
+                    .clamp(-1.0, 1.0),
+            ),
+            TakeoffPhase::Climb => None,
+        }
+    }
+
+    /// Whether the aircraft is high enough (altitude in feet) to hand off to a climb mode.
+    pub fn is_complete(&self, altitude: f64) -> bool {
+        self.phase == TakeoffPhase::Climb && altitude - self.field_elevation > HANDOFF_HEIGHT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_and_climb() {
+        let mut takeoff = Takeoff::new(90.0, 60.0, 8.0, 500.0);
+        takeoff.update(40.0, true);
+        assert_eq!(takeoff.phase, TakeoffPhase::Roll);
+        assert_eq!(takeoff.pitch(1.0), 0.0);
+        assert!(takeoff.rudder(85.0).unwrap() > 0.0);
+
+        takeoff.update(61.0, true);
+        assert_eq!(takeoff.phase, TakeoffPhase::Rotate);
+        assert_eq!(takeoff.pitch(1.0), ROTATION_RATE);
+        assert_eq!(takeoff.pitch(10.0), 8.0);
+
+        takeoff.update(70.0, false);
+        assert_eq!(takeoff.phase, TakeoffPhase::Climb);
+        assert_eq!(takeoff.rudder(85.0), None);
+        assert!(!takeoff.is_complete(700.0));
+        assert!(takeoff.is_complete(1000.0));
+    }
+}
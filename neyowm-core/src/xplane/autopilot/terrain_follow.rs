@@ -0,0 +1,122 @@
+use crate::{TerrainClearance, TerrainStatus};
+
+/// Climb rate (feet per minute) commanded per foot off the clearance over the terrain below.
+const HEIGHT_GAIN: f64 = 4.0;
+
+/// Steepest climb and descent (feet per minute) asked for. Descents are kept gentle, as the
+/// terrain ahead is only seen a few points at a time.
+const MAX_CLIMB: f64 = 2000.0;
+const MAX_DESCENT: f64 = 500.0;
+
+/// Seconds between reports of the clearance.
+const UPDATE_INTERVAL: f64 = 1.0;
+
+/// Climbs and descends to stay a clearance above the terrain. The terrain below sets the
+/// height held, and any rise ahead starts the climb early enough to be over it at the
+/// clearance by the time it is reached.
+pub struct TerrainFollow {
+    clearance: f64,
+    since_update: Option<f64>,
+}
+
+impl TerrainFollow {
+    /// Clearance is in feet.
+    pub fn new(clearance: f64) -> Self {
+        Self {
+            clearance,
+            since_update: None,
+        }
+    }
+
+    /// Climb rate (feet per minute) at the altitude over the terrain below, both in feet MSL.
+    /// The terrain ahead is given as distances (nautical miles) and elevations (feet MSL),
+    /// covered at the speed (knots). It only ever holds the aircraft up: coming down is left
+    /// to the height over the terrain below.
+    pub fn climb_rate(&self, altitude: f64, below: f64, ahead: &[(f64, f64)], speed: f64) -> f64 {
+        let height_rate = HEIGHT_GAIN * (below + self.clearance - altitude);
+        let ahead_rate = ahead
+            .iter()
+            .filter(|(distance, _)| *distance > 0.0)
+            .map(|(distance, elevation)| {
+                let minutes = distance / speed.max(1.0) * 60.0;
+                (elevation + self.clearance - altitude) / minutes
+            })
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        let climb_rate = if ahead_rate > 0.0 {
+            height_rate.max(ahead_rate)
+        } else {
+            height_rate
+        };
+        climb_rate.clamp(-MAX_DESCENT, MAX_CLIMB)
+    }
+
+    /// Reports the height over the terrain below and the least clearance ahead every so
+    /// often, with the same units as the climb rate.
+    pub fn update(
+        &mut self,
+        altitude: f64,
+        below: f64,
+        ahead: &[(f64, f64)],
+        dt: f64,
+    ) -> Option<TerrainStatus> {
+        if let Some(since_update) = &mut self.since_update {
+            *since_update += dt;
+            if *since_update < UPDATE_INTERVAL {
+                return None;
+            }
+        }
+        self.since_update = Some(0.0);
+
+        Some(TerrainStatus {
+            height: altitude - below,
+            ahead: ahead
+                .iter()
+                .map(|(distance, elevation)| TerrainClearance {
+                    distance: *distance,
+                    clearance: altitude - elevation,
+                })
+                .min_by(|a, b| a.clearance.total_cmp(&b.clearance)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_holds_clearance_over_flat_terrain() {
+        let follow = TerrainFollow::new(500.0);
+        let flat = [(0.5, 1000.0), (1.0, 1000.0), (3.0, 1000.0)];
+
+        assert_eq!(follow.climb_rate(1500.0, 1000.0, &flat, 100.0), 0.0);
+        assert_eq!(follow.climb_rate(1450.0, 1000.0, &flat, 100.0), 200.0);
+        assert_eq!(
+            follow.climb_rate(2500.0, 1000.0, &flat, 100.0),
+            -MAX_DESCENT
+        );
+    }
+
+    #[test]
+    fn test_climbs_ahead_of_rising_terrain() {
+        let follow = TerrainFollow::new(500.0);
+
+        // A ridge 800 ft up, two nautical miles (72 seconds) out
+        let ridge = [(1.0, 1000.0), (2.0, 1800.0), (3.0, 1200.0)];
+        let climb_rate = follow.climb_rate(1500.0, 1000.0, &ridge, 100.0);
+        assert!((climb_rate - 666.7).abs() < 0.1);
+
+        let mut follow = TerrainFollow::new(500.0);
+        let status = follow.update(1500.0, 1000.0, &ridge, 0.1).unwrap();
+        assert_eq!(status.height, 500.0);
+        assert_eq!(
+            status.ahead,
+            Some(TerrainClearance {
+                distance: 2.0,
+                clearance: -300.0
+            })
+        );
+        assert!(follow.update(1500.0, 1000.0, &ridge, 0.1).is_none());
+    }
+}
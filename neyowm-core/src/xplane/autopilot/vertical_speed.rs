@@ -0,0 +1,90 @@
+/// Pitch attitude (degrees) commanded per foot-per-minute of climb rate error.
+const CLIMB_RATE_GAIN: f64 = 0.004;
+
+/// Rate (degrees per second, per foot-per-minute of error) at which the pitch trim soaks up
+/// a steady climb rate error.
+const TRIM_GAIN: f64 = 0.0005;
+
+/// Time constant (seconds) of the low-pass filter on the estimated climb rate.
+const CLIMB_RATE_FILTER_TIME_CONSTANT: f64 = 1.0;
+
+pub const MIN_PITCH: f64 = -5.0;
+pub const MAX_PITCH: f64 = 10.0;
+
+/// Turns a climb rate error into a pitch target for the inner pitch PID.
+pub struct VerticalSpeedHold {
+    trim: f64,
+}
+
+impl VerticalSpeedHold {
+    pub fn new(trim: f64) -> Self {
+        Self {
+            trim: trim.clamp(MIN_PITCH, MAX_PITCH),
+        }
+    }
+
+    /// Climb rates are in feet per minute and dt in seconds.
+    pub fn pitch_target(&mut self, target: f64, climb_rate: f64, dt: f64) -> f64 {
+        let error = target - climb_rate;
+        self.trim = (self.trim + TRIM_GAIN * error * dt).clamp(MIN_PITCH, MAX_PITCH);
+        (self.trim + CLIMB_RATE_GAIN * error).clamp(MIN_PITCH, MAX_PITCH)
+    }
+}
+
+/// Estimates climb rate (feet per minute) from successive timestamped altitude samples (feet),
+/// low-pass filtered to keep position jitter out of the pitch loop.
+pub struct ClimbRateEstimator {
+    altitude: Option<f64>,
+    climb_rate: f64,
+}
+
+impl ClimbRateEstimator {
+    pub fn new() -> Self {
+        Self {
+            altitude: None,
+            climb_rate: 0.0,
+        }
+    }
+
+    pub fn altitude(&self) -> Option<f64> {
+        self.altitude
+    }
+
+    pub fn climb_rate(&self) -> f64 {
+        self.climb_rate
+    }
+
+    pub fn update(&mut self, altitude: f64, dt: f64) {
+        if let Some(previous_altitude) = self.altitude {
+            if dt > 0.0 {
+                let climb_rate = (altitude - previous_altitude) / dt * 60.0;
+                let alpha = dt / (CLIMB_RATE_FILTER_TIME_CONSTANT + dt);
+                self.climb_rate += alpha * (climb_rate - self.climb_rate);
+            }
+        }
+
+        self.altitude = Some(altitude);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimator_converges() {
+        let mut estimator = ClimbRateEstimator::new();
+        for i in 0..100 {
+            estimator.update(1000.0 + i as f64 * 2.5, 0.2);
+        }
+
+        assert!((estimator.climb_rate() - 750.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_pitch_target_is_limited() {
+        let mut hold = VerticalSpeedHold::new(2.0);
+        assert_eq!(hold.pitch_target(5_000.0, 0.0, 0.1), MAX_PITCH);
+        assert_eq!(hold.pitch_target(-5_000.0, 0.0, 0.1), MIN_PITCH);
+    }
+}
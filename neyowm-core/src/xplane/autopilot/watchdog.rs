@@ -0,0 +1,100 @@
+use std::time::{Duration, Instant};
+
+use crate::{ConnectionStatus, TelemetryInput};
+
+/// Age past which attitude is too old to fly on. Orientation normally arrives a few times a
+/// second, so this allows for a couple of missed reads.
+const ATTITUDE_MAX_AGE: Duration = Duration::from_millis(1000);
+
+/// Age past which the slower moving inputs are too old to fly on.
+const MAX_AGE: Duration = Duration::from_millis(2000);
+
+/// Tracks when each input the autopilot flies on last arrived, and whether the sim is
+/// answering at all.
+pub struct Watchdog {
+    attitude: Option<Instant>,
+    position: Option<Instant>,
+    airspeed: Option<Instant>,
+    nav1: Option<Instant>,
+    terrain: Option<Instant>,
+    terrain_ahead: Option<Instant>,
+    disconnected: bool,
+}
+
+impl Watchdog {
+    pub fn new() -> Self {
+        Self {
+            attitude: None,
+            position: None,
+            airspeed: None,
+            nav1: None,
+            terrain: None,
+            terrain_ahead: None,
+            disconnected: false,
+        }
+    }
+
+    pub fn received(&mut self, input: TelemetryInput, now: Instant) {
+        *self.received_mut(input) = Some(now);
+    }
+
+    fn received_mut(&mut self, input: TelemetryInput) -> &mut Option<Instant> {
+        match input {
+            TelemetryInput::Attitude => &mut self.attitude,
+            TelemetryInput::Position => &mut self.position,
+            TelemetryInput::Airspeed => &mut self.airspeed,
+            TelemetryInput::Nav1 => &mut self.nav1,
+            TelemetryInput::Terrain => &mut self.terrain,
+            TelemetryInput::TerrainAhead => &mut self.terrain_ahead,
+        }
+    }
+
+    /// Forgets an input ever arrived, for one that only arrives while it is asked for.
+    pub fn forget(&mut self, input: TelemetryInput) {
+        *self.received_mut(input) = None;
+    }
+
+    pub fn connection(&mut self, status: &ConnectionStatus) {
+        self.disconnected = *status == ConnectionStatus::Disconnected;
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    /// Whether an input has stopped arriving. One that has never arrived is not stale: the
+    /// modes that need it have nothing to act on yet.
+    pub fn is_stale(&self, input: TelemetryInput, now: Instant) -> bool {
+        let (received, max_age) = match input {
+            TelemetryInput::Attitude => (self.attitude, ATTITUDE_MAX_AGE),
+            TelemetryInput::Position => (self.position, MAX_AGE),
+            TelemetryInput::Airspeed => (self.airspeed, MAX_AGE),
+            TelemetryInput::Nav1 => (self.nav1, MAX_AGE),
+            TelemetryInput::Terrain => (self.terrain, MAX_AGE),
+            TelemetryInput::TerrainAhead => (self.terrain_ahead, MAX_AGE),
+        };
+
+        received.is_some_and(|received| now.duration_since(received) > max_age)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_inputs() {
+        let start = Instant::now();
+        let mut watchdog = Watchdog::new();
+        assert!(!watchdog.is_stale(TelemetryInput::Attitude, start + MAX_AGE * 2));
+
+        watchdog.received(TelemetryInput::Attitude, start);
+        watchdog.received(TelemetryInput::Position, start);
+        let later = start + Duration::from_millis(1500);
+        assert!(watchdog.is_stale(TelemetryInput::Attitude, later));
+        assert!(!watchdog.is_stale(TelemetryInput::Position, later));
+
+        watchdog.connection(&ConnectionStatus::Disconnected);
+        assert!(watchdog.is_disconnected());
+    }
+}
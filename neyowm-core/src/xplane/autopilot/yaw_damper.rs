@@ -0,0 +1,46 @@
+/// Rudder (fraction of full travel) commanded per degree of sideslip.
+const SIDESLIP_GAIN: f64 = 0.05;
+
+/// Rudder trim (fraction of full travel) accumulated per degree of sideslip per second.
+const SIDESLIP_TRIM: f64 = 0.01;
+
+/// Most rudder the yaw damper will use, leaving the rest to the pilot.
+const MAX_RUDDER: f64 = 0.5;
+
+/// Yaw damper and turn coordinator: drives the rudder to center the slip ball. The trim term
+/// takes out steady sideslip such as the adverse yaw held through a turn.
+pub struct YawDamper {
+    trim: f64,
+}
+
+impl YawDamper {
+    /// Trim starts at the given rudder position (fraction of full travel).
+    pub fn new(trim: f64) -> Self {
+        Self {
+            trim: trim.clamp(-MAX_RUDDER, MAX_RUDDER),
+        }
+    }
+
+    /// Sideslip in degrees, positive with the relative wind from the right, which takes
+    /// right (positive) rudder to correct.
+    pub fn rudder(&mut self, sideslip: f64, dt: f64) -> f64 {
+        self.trim = (self.trim + SIDESLIP_TRIM * sideslip * dt).clamp(-MAX_RUDDER, MAX_RUDDER);
+        (self.trim + SIDESLIP_GAIN * sideslip).clamp(-MAX_RUDDER, MAX_RUDDER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trims_out_steady_sideslip() {
+        let mut yaw_damper = YawDamper::new(0.0);
+        let first = yaw_damper.rudder(2.0, 1.0);
+        assert!(first > 0.0);
+
+        let later = (0..10).map(|_| yaw_damper.rudder(2.0, 1.0)).last().unwrap();
+        assert!(later > first);
+        assert!(yaw_damper.rudder(-20.0, 0.0) >= -MAX_RUDDER);
+    }
+}
@@ -1,4 +1,4 @@
-mod autopilot;
+pub(crate) mod autopilot;
 mod monitor;
 mod telemetry;
 
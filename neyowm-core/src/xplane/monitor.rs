@@ -7,9 +7,12 @@ use xplaneconnect::XPlaneConnection;
 
 use crate::{
     server::Bridge,
-    types::{ClientBoundMessage, ServerBoundMessage},
+    types::{ClientBoundMessage, ServerBoundMessage, TelemetryUpdate},
 };
 
+// Byte array holding the loaded aircraft's ICAO type designator, padded with zeros
+const ICAO_DATAREF: &str = "sim/aircraft/view/acf_ICAO";
+
 pub struct Monitor {
     connection: Arc<Mutex<XPlaneConnection>>,
 }
@@ -29,6 +32,23 @@ impl Monitor {
                                 crate::ConnectionStatus::Connected,
                             ),
                         ));
+
+                        if let Ok(data) = connection.read_datarefs(&[ICAO_DATAREF]) {
+                            let icao = data
+                                .first()
+                                .into_iter()
+                                .flatten()
+                                .map(|&byte| byte as u8)
+                                .take_while(|&byte| byte != 0)
+                                .map(char::from)
+                                .collect::<String>();
+
+                            _ = tx.send(ServerBoundMessage::Broadcast(
+                                ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Aircraft {
+                                    icao,
+                                }),
+                            ));
+                        }
                     }
                     Err(_) => {
                         _ = tx.send(ServerBoundMessage::Broadcast(
@@ -7,9 +7,38 @@ use xplaneconnect::XPlaneConnection;
 
 use crate::{
     server::Bridge,
-    types::{ClientBoundMessage, ServerBoundMessage, TelemetryUpdate},
+    types::{
+        AutopilotMode, ClientBoundMessage, ServerBoundMessage, TelemetryUpdate, TerrainSample,
+        Waypoint,
+    },
 };
 
+use super::autopilot::navigation::{bearing, destination, distance};
+
+// Read together in one request; the order here is the order of the values read back
+const DATAREFS: [&str; 9] = [
+    "sim/flightmodel/position/indicated_airspeed",
+    "sim/cockpit/radios/nav1_hdef_dot",
+    "sim/cockpit/radios/nav1_vdef_dot",
+    "sim/cockpit2/radios/indicators/nav1_display_horizontal",
+    "sim/cockpit2/radios/indicators/nav1_display_vertical",
+    "sim/flightmodel/failures/onground_any",
+    "sim/flightmodel/position/beta",
+    "sim/flightmodel/forces/g_nrml",
+    "sim/cockpit2/annunciators/stall_warning",
+];
+
+// Read alongside the position so attitude can be timed against the sim clock
+const SIM_TIME_DATAREF: &str = "sim/time/total_running_time_sec";
+
+// Nautical miles ahead along the track at which the terrain is probed, one point per read
+// so a round trip for each does not hold up the rest of the telemetry
+const TERRAIN_LOOKAHEAD: [f64; 5] = [0.25, 0.5, 1.0, 2.0, 3.0];
+
+// Nautical miles moved between position reads below which the track is taken to be the
+// heading, as it is too noisy to work out
+const MIN_TRACK_DISTANCE: f64 = 0.005;
+
 pub struct Telemetry {
     connection: Arc<Mutex<XPlaneConnection>>,
 }
@@ -21,11 +50,54 @@ impl Telemetry {
 
     pub fn run(self, bridge: Bridge, interval: Duration) {
         let mut ticker = 0;
-        bridge.recv_with_interval(interval, |_, tx| {
+        // Where the aircraft was last read, at what altitude (meters MSL), and its track
+        let mut last_position: Option<(Waypoint, f64, f64)> = None;
+        // Terrain ahead is only probed while a mode is flying on it, each point in turn
+        let mut terrain_wanted = false;
+        let mut terrain_ahead: [Option<TerrainSample>; TERRAIN_LOOKAHEAD.len()] =
+            [None; TERRAIN_LOOKAHEAD.len()];
+        let mut next_probe = 0;
+        bridge.recv_with_interval(interval, |queue, tx| {
+            for msg in queue {
+                if let ClientBoundMessage::AutopilotStatus(status) = msg {
+                    terrain_wanted =
+                        matches!(status.mode, Some(AutopilotMode::TerrainFollow { .. }));
+                    if !terrain_wanted {
+                        terrain_ahead = [None; TERRAIN_LOOKAHEAD.len()];
+                    }
+                }
+            }
+
             match ticker {
                 0 => {
                     if let Ok(connection) = self.connection.try_lock() {
                         if let Ok(data) = connection.read_position() {
+                            let position = Waypoint {
+                                latitude: data.latitude,
+                                longitude: data.longitude,
+                            };
+                            let track = match &last_position {
+                                Some((last, _, _))
+                                    if distance(last, &position) > MIN_TRACK_DISTANCE =>
+                                {
+                                    bearing(last, &position)
+                                }
+                                _ => data.yaw,
+                            };
+                            last_position = Some((position, data.altitude, track));
+
+                            if let Ok(time) = connection.read_datarefs(&[SIM_TIME_DATAREF]) {
+                                if let Some(seconds) =
+                                    time.first().and_then(|values| values.first()).copied()
+                                {
+                                    _ = tx.send(ServerBoundMessage::Broadcast(
+                                        ClientBoundMessage::UpdateTelemetry(
+                                            TelemetryUpdate::SimTime { seconds },
+                                        ),
+                                    ));
+                                }
+                            }
+
                             _ = tx.send(ServerBoundMessage::Broadcast(
                                 ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Orientation {
                                     pitch: data.pitch,
@@ -75,11 +147,114 @@ impl Telemetry {
                             ));
                         }
                     }
+
+                    // Locked again for the datarefs, so the other reads can get in between
+                    if let Ok(connection) = self.connection.try_lock() {
+                        if let Ok(data) = connection.read_datarefs(&DATAREFS) {
+                            let value = |index: usize| data.get(index)?.first().copied();
+
+                            if let Some(indicated) = value(0) {
+                                _ = tx.send(ServerBoundMessage::Broadcast(
+                                    ClientBoundMessage::UpdateTelemetry(
+                                        TelemetryUpdate::Airspeed { indicated },
+                                    ),
+                                ));
+                            }
+
+                            if let Some(on_ground) = value(5) {
+                                _ = tx.send(ServerBoundMessage::Broadcast(
+                                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Ground {
+                                        on_ground: on_ground != 0.0,
+                                    }),
+                                ));
+                            }
+
+                            if let Some(beta) = value(6) {
+                                _ = tx.send(ServerBoundMessage::Broadcast(
+                                    ClientBoundMessage::UpdateTelemetry(
+                                        TelemetryUpdate::Sideslip { beta },
+                                    ),
+                                ));
+                            }
+
+                            if let Some(normal) = value(7) {
+                                _ = tx.send(ServerBoundMessage::Broadcast(
+                                    ClientBoundMessage::UpdateTelemetry(
+                                        TelemetryUpdate::LoadFactor { normal },
+                                    ),
+                                ));
+                            }
+
+                            if let Some(warning) = value(8) {
+                                _ = tx.send(ServerBoundMessage::Broadcast(
+                                    ClientBoundMessage::UpdateTelemetry(
+                                        TelemetryUpdate::StallWarning {
+                                            warning: warning != 0.0,
+                                        },
+                                    ),
+                                ));
+                            }
+
+                            if let (
+                                Some(localizer),
+                                Some(glideslope),
+                                Some(localizer_valid),
+                                Some(glideslope_valid),
+                            ) = (value(1), value(2), value(3), value(4))
+                            {
+                                _ = tx.send(ServerBoundMessage::Broadcast(
+                                    ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Nav1 {
+                                        localizer,
+                                        glideslope,
+                                        localizer_valid: localizer_valid != 0.0,
+                                        glideslope_valid: glideslope_valid != 0.0,
+                                    }),
+                                ));
+                            }
+                        }
+                    }
+                }
+                3 => {
+                    if let Some((position, altitude, track)) =
+                        last_position.as_ref().filter(|_| terrain_wanted)
+                    {
+                        let distance = TERRAIN_LOOKAHEAD[next_probe];
+                        let point = destination(position, *track, distance);
+                        if let Ok(connection) = self.connection.try_lock() {
+                            if let Ok(data) = connection.read_terrain_at(
+                                point.latitude,
+                                point.longitude,
+                                *altitude,
+                            ) {
+                                terrain_ahead[next_probe] = Some(TerrainSample {
+                                    distance,
+                                    elevation: data.elevation,
+                                });
+                                next_probe = (next_probe + 1) % TERRAIN_LOOKAHEAD.len();
+
+                                _ = tx.send(ServerBoundMessage::Broadcast(
+                                    ClientBoundMessage::UpdateTelemetry(
+                                        TelemetryUpdate::TerrainProfile {
+                                            track: *track,
+                                            samples: terrain_ahead
+                                                .iter()
+                                                .flatten()
+                                                .copied()
+                                                .collect(),
+                                        },
+                                    ),
+                                ));
+                            }
+                        }
+                    }
                 }
                 _ => {}
             }
 
-            ticker = (ticker + 1usize) % 3;
+            // The terrain ahead only takes a turn while it is wanted, so the rest of the time
+            // the attitude is read every third turn
+            let slots = if terrain_wanted { 4 } else { 3 };
+            ticker = (ticker + 1usize) % slots;
         });
     }
 }
@@ -14,7 +14,9 @@ use ratatui::{
     Terminal,
 };
 
-use crate::ui::{AutopilotView, CommandView, ConnectionView, FlightVectorView, TelemetryView};
+use crate::ui::{
+    AutopilotView, CommandView, ConnectionView, FlightVectorView, TelemetryView, TuningView,
+};
 
 fn is_exit_event(event: &Event) -> bool {
     matches!(event, Event::Key(KeyEvent { code: KeyCode::Char('c'), modifiers, .. }) if modifiers.contains(KeyModifiers::CONTROL))
@@ -77,6 +79,8 @@ impl App {
             connection_view: ConnectionView::new(),
             flight_vector_view: FlightVectorView::new(),
             telemetry_view: TelemetryView::new(),
+            tuning_view: TuningView::new(),
+            tuning_visible: false,
 
             _phantom: std::marker::PhantomData,
         };
@@ -144,6 +148,8 @@ struct ViewState<'a> {
     connection_view: ConnectionView,
     flight_vector_view: FlightVectorView,
     telemetry_view: TelemetryView,
+    tuning_view: TuningView,
+    tuning_visible: bool,
 
     _phantom: std::marker::PhantomData<&'a ()>,
 }
@@ -184,17 +190,39 @@ impl<'a> ViewState<'a> {
                     .constraints(
                         [
                             ratatui::layout::Constraint::Min(28),
-                            ratatui::layout::Constraint::Length(
-                                ((layout[1].height - 2) * 2) as u16,
-                            ),
+                            ratatui::layout::Constraint::Length((layout[1].height - 2) * 2),
                             ratatui::layout::Constraint::Min(44),
                         ]
                         .as_ref(),
                     )
                     .split(layout[1]);
 
+                // Render the tuning view in place of the telemetry and the flight vector
+                if self.tuning_visible {
+                    let area = layout[0].union(layout[1]).inner(Margin::new(1, 0));
+                    let block = Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .title(" TUNING [u] ")
+                        .border_style({
+                            if self.view_in_focus == Some(FocusedView::Tuning) {
+                                ratatui::style::Style::default().fg(Color::Yellow).bold()
+                            } else {
+                                ratatui::style::Style::default().fg(Color::DarkGray)
+                            }
+                        });
+
+                    frame.render_widget(block, area);
+                    self.tuning_view.draw(
+                        area.inner(DEFAULT_MARGINS),
+                        frame,
+                        &mut effects,
+                        self.view_in_focus == Some(FocusedView::Tuning),
+                    );
+                }
+
                 // Render the telemetry view
-                {
+                if !self.tuning_visible {
                     let area = layout[0].inner(Margin::new(1, 0));
                     let block = Block::default()
                         .borders(Borders::ALL)
@@ -207,7 +235,7 @@ impl<'a> ViewState<'a> {
                 }
 
                 // Render the canvas view
-                {
+                if !self.tuning_visible {
                     let area = layout[1].inner(Margin::new(1, 0)).inner(Margin::new(5, 5));
                     self.flight_vector_view.draw(area, frame);
                 }
@@ -289,6 +317,20 @@ impl<'a> ViewState<'a> {
                 self.view_in_focus = Some(FocusedView::AutoPilotView);
                 false
             }
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('u'),
+                ..
+            }) if event_utils::is_nav_event(&event) => {
+                self.tuning_visible = !self.tuning_visible;
+                self.view_in_focus = if self.tuning_visible {
+                    Some(FocusedView::Tuning)
+                } else if self.view_in_focus == Some(FocusedView::Tuning) {
+                    None
+                } else {
+                    self.view_in_focus
+                };
+                false
+            }
             _ => true,
         };
 
@@ -300,6 +342,9 @@ impl<'a> ViewState<'a> {
                 Some(FocusedView::AutoPilotView) => {
                     self.autopilot_view.handle_user_event(bridge, event);
                 }
+                Some(FocusedView::Tuning) => {
+                    self.tuning_view.handle_user_event(bridge, event);
+                }
                 None => {
                     // Noop
                 }
@@ -317,6 +362,7 @@ impl<'a> ViewState<'a> {
         self.connection_view.handle_client_message(bridge, &msg);
         self.flight_vector_view.handle_client_message(bridge, &msg);
         self.telemetry_view.handle_client_message(bridge, &msg);
+        self.tuning_view.handle_client_message(bridge, &msg);
 
         true
     }
@@ -340,6 +386,7 @@ impl BridgeSink {
 pub enum FocusedView {
     AutoPilotView,
     CommandView,
+    Tuning,
 }
 
 pub struct Effects {
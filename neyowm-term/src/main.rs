@@ -1,7 +1,7 @@
-use std::{net::Ipv4Addr, sync::Arc, time::Duration};
+use std::{net::Ipv4Addr, path::Path, sync::Arc, time::Duration};
 
 use app::App;
-use neyowm::{xplane, ClientBoundMessage, Server};
+use neyowm::{xplane, AircraftProfile, Airport, ClientBoundMessage, Server};
 use xplaneconnect::XPlaneConnection;
 
 mod app;
@@ -12,7 +12,9 @@ fn main() {
     let shared_connection = Arc::new(connection);
     let xplane_monitor = xplane::Monitor::new(shared_connection.clone());
     let xplane_telemetry = xplane::Telemetry::new(shared_connection.clone());
-    let xplane_autopilot = xplane::Autopilot::new(shared_connection.clone());
+    let xplane_autopilot = xplane::Autopilot::new(shared_connection.clone())
+        .with_profiles(aircraft_profiles())
+        .with_airports(airports());
 
     let mut server = Server::new();
 
@@ -47,3 +49,42 @@ fn main() {
 
     server.run();
 }
+
+// Built-in profiles, followed by any in the directory given with `--profiles <dir>`
+fn aircraft_profiles() -> Vec<AircraftProfile> {
+    let mut profiles = AircraftProfile::builtin();
+
+    let args = std::env::args().collect::<Vec<_>>();
+    if let Some(dir) = args
+        .iter()
+        .position(|arg| arg == "--profiles")
+        .and_then(|index| args.get(index + 1))
+    {
+        match AircraftProfile::load_dir(Path::new(dir)) {
+            Ok(loaded) => profiles.extend(loaded),
+            Err(error) => eprintln!("Unable to load aircraft profiles: {:?}", error),
+        }
+    }
+
+    profiles
+}
+
+// Airports to glide for, from the `apt.dat` file given with `--airports <file>`
+fn airports() -> Vec<Airport> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let Some(path) = args
+        .iter()
+        .position(|arg| arg == "--airports")
+        .and_then(|index| args.get(index + 1))
+    else {
+        return Vec::new();
+    };
+
+    match Airport::load(Path::new(path)) {
+        Ok(airports) => airports,
+        Err(error) => {
+            eprintln!("Unable to load airports: {:?}", error);
+            Vec::new()
+        }
+    }
+}
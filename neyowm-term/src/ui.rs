@@ -2,10 +2,13 @@ mod autopilot_view;
 mod command_view;
 mod connection_view;
 mod flight_vector_view;
+mod form;
 mod telemetry_view;
+mod tuning_view;
 
 pub use autopilot_view::AutopilotView;
 pub use command_view::CommandView;
 pub use connection_view::ConnectionView;
 pub use flight_vector_view::FlightVectorView;
 pub use telemetry_view::TelemetryView;
+pub use tuning_view::TuningView;
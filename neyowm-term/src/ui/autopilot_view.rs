@@ -1,5 +1,9 @@
 use crossterm::event::{Event, KeyCode};
-use neyowm::{AutopilotMode, ClientBoundMessage};
+use neyowm::{
+    AutopilotAlert, AutopilotMode, AutopilotStatus, AutothrottleMode, ClientBoundMessage,
+    GlideStatus, HoldLeg, LateralMode, ProfileSelection, ProfileStatus, RecoveryPhase,
+    TelemetryInput, TerrainStatus, ThrustMode, TurnDirection, Upset, VerticalMode, Waypoint,
+};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Margin, Offset, Rect},
     style::{Color, Style, Stylize},
@@ -8,12 +12,32 @@ use ratatui::{
     Frame,
 };
 
+use super::form::{Field, Form};
 use crate::app::{event_utils, BridgeSink, Effects};
 
 pub struct AutopilotView {
     mode: AutopilotModeKind,
     active_mode: AutopilotModeKind,
     autopilot_hold_form: Form<AutopilotHoldState>,
+    autopilot_altitude_form: Form<AutopilotAltitudeState>,
+    autopilot_heading_form: Form<AutopilotHeadingState>,
+    autopilot_vertical_speed_form: Form<AutopilotVerticalSpeedState>,
+    autopilot_lnav_form: Form<AutopilotLnavState>,
+    autopilot_approach_form: Form<AutopilotApproachState>,
+    autopilot_takeoff_form: Form<AutopilotTakeoffState>,
+    autopilot_orbit_form: Form<AutopilotOrbitState>,
+    autopilot_holding_form: Form<AutopilotHoldingState>,
+    autopilot_glide_form: Form<AutopilotGlideState>,
+    autopilot_go_around_form: Form<AutopilotGoAroundState>,
+    autopilot_terrain_form: Form<AutopilotTerrainState>,
+    glide: Option<GlideStatus>,
+    terrain: Option<TerrainStatus>,
+    status: AutopilotStatus,
+    alert: Option<AutopilotAlert>,
+    profile: Option<ProfileStatus>,
+    autothrottle_form: Form<AutothrottleState>,
+    autothrottle_active: bool,
+    autothrottle_in_focus: bool,
 }
 
 impl AutopilotView {
@@ -21,14 +45,82 @@ impl AutopilotView {
         let autopilot_hold_state = AutopilotHoldState {
             roll: 0.0,
             pitch: 2.0,
+        };
+
+        let autopilot_altitude_state = AutopilotAltitudeState {
+            roll: 0.0,
+            altitude: 10_000.0,
+        };
+
+        let autopilot_heading_state = AutopilotHeadingState {
+            heading: 0.0,
+            bank_limit: 20.0,
             altitude: 10_000.0,
         };
 
+        let autopilot_vertical_speed_state = AutopilotVerticalSpeedState {
+            fpm: 500.0,
+            altitude: None,
+        };
+
+        let autopilot_lnav_state = AutopilotLnavState {
+            waypoints: Vec::new(),
+            bank_limit: 20.0,
+            altitude: 10_000.0,
+        };
+
+        let autopilot_approach_state = AutopilotApproachState {
+            heading: 0.0,
+            course: 0.0,
+            bank_limit: 20.0,
+            altitude: 10_000.0,
+            flare_height: Some(30.0),
+        };
+
+        let autopilot_takeoff_state = AutopilotTakeoffState {
+            heading: 0.0,
+            rotate_speed: 60.0,
+            rotate_pitch: 8.0,
+            altitude: 3_000.0,
+        };
+
+        let autopilot_orbit_state = AutopilotOrbitState {
+            center: None,
+            radius: 2.0,
+            direction: TurnDirection::Left,
+            bank_limit: 20.0,
+            altitude: 3_000.0,
+        };
+
+        let autopilot_holding_state = AutopilotHoldingState {
+            fix: None,
+            inbound_course: 0.0,
+            leg: HoldLeg::Time { minutes: 1.0 },
+            direction: TurnDirection::Right,
+            bank_limit: 20.0,
+            altitude: 3_000.0,
+        };
+
+        let autopilot_glide_state = AutopilotGlideState { bank_limit: 20.0 };
+
+        let autopilot_go_around_state = AutopilotGoAroundState {
+            heading: None,
+            altitude: 3_000.0,
+        };
+
+        let autopilot_terrain_state = AutopilotTerrainState {
+            heading: 0.0,
+            bank_limit: 20.0,
+            clearance: 500.0,
+        };
+
+        let autothrottle_state = AutothrottleState { knots: 100.0 };
+
         AutopilotView {
             mode: AutopilotModeKind::Off,
             active_mode: AutopilotModeKind::Off,
             autopilot_hold_form: Form::new(
-                autopilot_hold_state.clone(),
+                autopilot_hold_state,
                 vec![
                     Field::new(
                         "ROLL",
@@ -54,9 +146,199 @@ impl AutopilotView {
                             }
                         },
                     ),
+                ],
+            ),
+            autopilot_altitude_form: Form::new(
+                autopilot_altitude_state,
+                vec![
+                    Field::new(
+                        "ROLL",
+                        format!("{:.01}", autopilot_altitude_state.roll),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.roll = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "ALT",
+                        format!("{}", autopilot_altitude_state.altitude),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.altitude = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            autopilot_heading_form: Form::new(
+                autopilot_heading_state,
+                vec![
+                    Field::new(
+                        "HDG",
+                        format!("{:03.0}", autopilot_heading_state.heading),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.heading = value;
+                                (0.0..=360.0).contains(&value)
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "BANK",
+                        format!("{:.01}", autopilot_heading_state.bank_limit),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.bank_limit = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "ALT",
+                        format!("{}", autopilot_heading_state.altitude),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.altitude = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            autopilot_vertical_speed_form: Form::new(
+                autopilot_vertical_speed_state,
+                vec![
+                    Field::new(
+                        "VS",
+                        format!("{}", autopilot_vertical_speed_state.fpm),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.fpm = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "ALT",
+                        autopilot_vertical_speed_state
+                            .altitude
+                            .map(|altitude| format!("{}", altitude))
+                            .unwrap_or_default(),
+                        |value, state| {
+                            // Left empty, the climb or descent goes on until disengaged
+                            if value.is_empty() {
+                                state.altitude = None;
+                                true
+                            } else if let Ok(value) = value.parse::<f64>() {
+                                state.altitude = Some(value);
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            autopilot_lnav_form: Form::new(
+                autopilot_lnav_state.clone(),
+                vec![
+                    Field::new("ROUTE", "", |value, state| {
+                        // Waypoints as space separated "lat,lon" pairs in decimal degrees
+                        let waypoints = value
+                            .split_whitespace()
+                            .map(parse_waypoint)
+                            .collect::<Option<Vec<_>>>();
+
+                        if let Some(waypoints) = waypoints {
+                            state.waypoints = waypoints;
+                            !state.waypoints.is_empty()
+                        } else {
+                            false
+                        }
+                    }),
+                    Field::new(
+                        "BANK",
+                        format!("{:.01}", autopilot_lnav_state.bank_limit),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.bank_limit = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "ALT",
+                        format!("{}", autopilot_lnav_state.altitude),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.altitude = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            autopilot_approach_form: Form::new(
+                autopilot_approach_state,
+                vec![
+                    Field::new(
+                        "HDG",
+                        format!("{:03.0}", autopilot_approach_state.heading),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.heading = value;
+                                (0.0..=360.0).contains(&value)
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "CRS",
+                        format!("{:03.0}", autopilot_approach_state.course),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.course = value;
+                                (0.0..=360.0).contains(&value)
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "BANK",
+                        format!("{:.01}", autopilot_approach_state.bank_limit),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.bank_limit = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
                     Field::new(
                         "ALT",
-                        format!("{}", autopilot_hold_state.altitude),
+                        format!("{}", autopilot_approach_state.altitude),
                         |value, state| {
                             if let Ok(value) = value.parse::<f64>() {
                                 state.altitude = value;
@@ -66,121 +348,990 @@ impl AutopilotView {
                             }
                         },
                     ),
+                    Field::new(
+                        "FLARE",
+                        autopilot_approach_state
+                            .flare_height
+                            .map(|height| format!("{}", height))
+                            .unwrap_or_default(),
+                        |value, state| {
+                            // Left empty, the approach is flown down to minimums only
+                            if value.is_empty() {
+                                state.flare_height = None;
+                                true
+                            } else if let Ok(value) = value.parse::<f64>() {
+                                state.flare_height = Some(value);
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
                 ],
             ),
+            autopilot_takeoff_form: Form::new(
+                autopilot_takeoff_state,
+                vec![
+                    Field::new(
+                        "RWY",
+                        format!("{:03.0}", autopilot_takeoff_state.heading),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.heading = value;
+                                (0.0..=360.0).contains(&value)
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "VR",
+                        format!("{}", autopilot_takeoff_state.rotate_speed),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.rotate_speed = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "PITCH",
+                        format!("{:.01}", autopilot_takeoff_state.rotate_pitch),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.rotate_pitch = value;
+                                (0.0..=20.0).contains(&value)
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "ALT",
+                        format!("{}", autopilot_takeoff_state.altitude),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.altitude = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            autopilot_orbit_form: Form::new(
+                autopilot_orbit_state.clone(),
+                vec![
+                    Field::new("FIX", "", |value, state| {
+                        state.center = parse_waypoint(value);
+                        state.center.is_some()
+                    }),
+                    Field::new(
+                        "RAD",
+                        format!("{:.01}", autopilot_orbit_state.radius),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.radius = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "DIR",
+                        turn_direction_label(autopilot_orbit_state.direction),
+                        |value, state| {
+                            if let Some(direction) = parse_turn_direction(value) {
+                                state.direction = direction;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "BANK",
+                        format!("{:.01}", autopilot_orbit_state.bank_limit),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.bank_limit = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "ALT",
+                        format!("{}", autopilot_orbit_state.altitude),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.altitude = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            autopilot_holding_form: Form::new(
+                autopilot_holding_state.clone(),
+                vec![
+                    Field::new("FIX", "", |value, state| {
+                        state.fix = parse_waypoint(value);
+                        state.fix.is_some()
+                    }),
+                    Field::new(
+                        "CRS",
+                        format!("{:03.0}", autopilot_holding_state.inbound_course),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.inbound_course = value;
+                                (0.0..=360.0).contains(&value)
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new("LEG", "1", |value, state| {
+                        // Minutes, or nautical miles with an NM suffix
+                        let upper = value.to_ascii_uppercase();
+                        let leg = match upper.strip_suffix("NM") {
+                            Some(nm) => nm.trim().parse::<f64>().map(|nm| HoldLeg::Distance { nm }),
+                            None => upper
+                                .parse::<f64>()
+                                .map(|minutes| HoldLeg::Time { minutes }),
+                        };
+
+                        match leg {
+                            Ok(leg) => {
+                                state.leg = leg;
+                                match leg {
+                                    HoldLeg::Time { minutes } => minutes > 0.0,
+                                    HoldLeg::Distance { nm } => nm > 0.0,
+                                }
+                            }
+                            Err(_) => false,
+                        }
+                    }),
+                    Field::new(
+                        "DIR",
+                        turn_direction_label(autopilot_holding_state.direction),
+                        |value, state| {
+                            if let Some(direction) = parse_turn_direction(value) {
+                                state.direction = direction;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "BANK",
+                        format!("{:.01}", autopilot_holding_state.bank_limit),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.bank_limit = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "ALT",
+                        format!("{}", autopilot_holding_state.altitude),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.altitude = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            autopilot_glide_form: Form::new(
+                autopilot_glide_state,
+                vec![Field::new(
+                    "BANK",
+                    format!("{:.01}", autopilot_glide_state.bank_limit),
+                    |value, state| {
+                        if let Ok(value) = value.parse::<f64>() {
+                            state.bank_limit = value;
+                            value > 0.0
+                        } else {
+                            false
+                        }
+                    },
+                )],
+            ),
+            autopilot_go_around_form: Form::new(
+                autopilot_go_around_state,
+                vec![
+                    Field::new("HDG", "", |value, state| {
+                        // Left empty, the go-around holds the heading it starts on
+                        if value.is_empty() {
+                            state.heading = None;
+                            true
+                        } else if let Ok(value) = value.parse::<f64>() {
+                            state.heading = Some(value);
+                            (0.0..=360.0).contains(&value)
+                        } else {
+                            false
+                        }
+                    }),
+                    Field::new(
+                        "ALT",
+                        format!("{}", autopilot_go_around_state.altitude),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.altitude = value;
+                                true
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            autopilot_terrain_form: Form::new(
+                autopilot_terrain_state,
+                vec![
+                    Field::new(
+                        "HDG",
+                        format!("{:03.0}", autopilot_terrain_state.heading),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.heading = value;
+                                (0.0..=360.0).contains(&value)
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "BANK",
+                        format!("{:.01}", autopilot_terrain_state.bank_limit),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.bank_limit = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                    Field::new(
+                        "CLR",
+                        format!("{}", autopilot_terrain_state.clearance),
+                        |value, state| {
+                            if let Ok(value) = value.parse::<f64>() {
+                                state.clearance = value;
+                                value > 0.0
+                            } else {
+                                false
+                            }
+                        },
+                    ),
+                ],
+            ),
+            glide: None,
+            terrain: None,
+            status: AutopilotStatus::default(),
+            alert: None,
+            profile: None,
+            autothrottle_form: Form::new(
+                autothrottle_state,
+                vec![Field::new(
+                    "SPD",
+                    format!("{}", autothrottle_state.knots),
+                    |value, state| {
+                        if let Ok(value) = value.parse::<f64>() {
+                            state.knots = value;
+                            value > 0.0
+                        } else {
+                            false
+                        }
+                    },
+                )],
+            ),
+            autothrottle_active: false,
+            autothrottle_in_focus: false,
+        }
+    }
+
+    pub fn handle_client_message(&mut self, _: &BridgeSink, msg: &ClientBoundMessage) {
+        match msg {
+            ClientBoundMessage::AutopilotStatus(status) => {
+                self.status = status.clone();
+
+                // The autopilot can change mode by itself, e.g. disengaging at the end of a
+                // rollout or handing off after a takeoff, so follow it to the new mode
+                let kind = status
+                    .mode
+                    .as_ref()
+                    .map(AutopilotModeKind::from)
+                    .unwrap_or(AutopilotModeKind::Off);
+                if kind != self.active_mode {
+                    if self.mode == self.active_mode {
+                        self.mode = kind;
+                    }
+                    self.active_mode = kind;
+                }
+            }
+            ClientBoundMessage::SetAutothrottleMode(AutothrottleMode::Off) => {
+                self.autothrottle_active = false;
+            }
+            ClientBoundMessage::AutopilotAlert(alert) => {
+                self.alert = Some(*alert);
+            }
+            ClientBoundMessage::ProfileStatus(profile) => {
+                self.profile = Some(profile.clone());
+            }
+            ClientBoundMessage::GlideStatus(glide) => {
+                self.glide = Some(glide.clone());
+            }
+            ClientBoundMessage::TerrainStatus(terrain) => {
+                self.terrain = Some(*terrain);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_user_event(&mut self, bridge: &BridgeSink, event: Event) {
+        if let Event::Key(key) = &event {
+            match key.code {
+                KeyCode::Char('1') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Off;
+                    bridge.broadcast(ClientBoundMessage::SetAutopilotMode(AutopilotMode::Off));
+                }
+                KeyCode::Char('2') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Hold;
+                }
+                KeyCode::Char('3') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Altitude;
+                }
+                KeyCode::Char('4') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Heading;
+                }
+                KeyCode::Char('5') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::VerticalSpeed;
+                }
+                KeyCode::Char('6') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Lnav;
+                }
+                KeyCode::Char('7') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Approach;
+                }
+                KeyCode::Char('8') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Takeoff;
+                }
+                KeyCode::Char('9') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Orbit;
+                }
+                KeyCode::Char('0') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Holding;
+                }
+                KeyCode::Char('g') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Glide;
+                }
+                KeyCode::Char('b') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::GoAround;
+                }
+                KeyCode::Char('e') if event_utils::is_nav_event(&event) => {
+                    self.mode = AutopilotModeKind::Terrain;
+                }
+                KeyCode::Char('p') if event_utils::is_nav_event(&event) => {
+                    if let Some(profile) = &self.profile {
+                        bridge.broadcast(ClientBoundMessage::SelectProfile(
+                            next_profile_selection(profile),
+                        ));
+                    }
+                }
+                KeyCode::Char('f') if event_utils::is_nav_event(&event) => {
+                    bridge.broadcast(ClientBoundMessage::SetFlightDirector(
+                        !self.status.flight_director,
+                    ));
+                }
+                KeyCode::Char('o') if event_utils::is_nav_event(&event) => {
+                    bridge.broadcast(ClientBoundMessage::SetUpsetProtection(
+                        !self.status.upset_protection,
+                    ));
+                }
+                KeyCode::Char('r') if event_utils::is_nav_event(&event) => {
+                    self.alert = None;
+                    bridge.broadcast(ClientBoundMessage::StartRecovery);
+                }
+                KeyCode::Char('s') if event_utils::is_nav_event(&event) => {
+                    self.autothrottle_in_focus = !self.autothrottle_in_focus;
+                }
+                KeyCode::Char('t') if event_utils::is_nav_event(&event) => {
+                    self.autothrottle_active =
+                        !self.autothrottle_active && self.autothrottle_form.is_valid();
+                    self.alert = None;
+                    self.broadcast_autothrottle_mode(bridge);
+                }
+                KeyCode::Enter if event_utils::is_nav_event(&event) => {
+                    self.alert = None;
+
+                    // Enter engages the selected mode, or sends new targets for the engaged
+                    // one, and disengages when there is nothing new to send. The mode only
+                    // shows as active once the autopilot reports it is
+                    match self.selected_mode() {
+                        Some(mode) if self.status.mode.as_ref() != Some(&mode) => {
+                            bridge.broadcast(ClientBoundMessage::SetAutopilotMode(mode));
+                        }
+                        _ => {
+                            bridge.broadcast(ClientBoundMessage::SetAutopilotMode(
+                                AutopilotMode::Off,
+                            ));
+                        }
+                    }
+                }
+                KeyCode::Enter if self.autothrottle_in_focus => {
+                    if self.autothrottle_active && self.autothrottle_form.is_valid() {
+                        self.broadcast_autothrottle_mode(bridge);
+                    }
+                }
+                _ if self.autothrottle_in_focus => {
+                    self.autothrottle_form.handle_user_event(event);
+                }
+                _ => {
+                    match self.mode {
+                        AutopilotModeKind::Hold => {
+                            self.autopilot_hold_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Altitude => {
+                            self.autopilot_altitude_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Heading => {
+                            self.autopilot_heading_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::VerticalSpeed => {
+                            self.autopilot_vertical_speed_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Lnav => {
+                            self.autopilot_lnav_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Approach => {
+                            self.autopilot_approach_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Takeoff => {
+                            self.autopilot_takeoff_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Orbit => {
+                            self.autopilot_orbit_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Holding => {
+                            self.autopilot_holding_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Glide => {
+                            self.autopilot_glide_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::GoAround => {
+                            self.autopilot_go_around_form.handle_user_event(event);
+                        }
+                        AutopilotModeKind::Terrain => {
+                            self.autopilot_terrain_form.handle_user_event(event);
+                        }
+                        _ => {}
+                    };
+                }
+            };
+        }
+    }
+
+    /// The mode selected on the tabs with the targets entered in its form, if they are valid.
+    fn selected_mode(&self) -> Option<AutopilotMode> {
+        let valid = match self.mode {
+            AutopilotModeKind::Off => true,
+            AutopilotModeKind::Hold => self.autopilot_hold_form.is_valid(),
+            AutopilotModeKind::Altitude => self.autopilot_altitude_form.is_valid(),
+            AutopilotModeKind::Heading => self.autopilot_heading_form.is_valid(),
+            AutopilotModeKind::VerticalSpeed => self.autopilot_vertical_speed_form.is_valid(),
+            AutopilotModeKind::Lnav => {
+                self.autopilot_lnav_form.is_valid()
+                    && !self.autopilot_lnav_form.state.waypoints.is_empty()
+            }
+            AutopilotModeKind::Approach => self.autopilot_approach_form.is_valid(),
+            AutopilotModeKind::Takeoff => self.autopilot_takeoff_form.is_valid(),
+            AutopilotModeKind::Orbit => self.autopilot_orbit_form.is_valid(),
+            AutopilotModeKind::Holding => self.autopilot_holding_form.is_valid(),
+            AutopilotModeKind::Glide => self.autopilot_glide_form.is_valid(),
+            AutopilotModeKind::GoAround => self.autopilot_go_around_form.is_valid(),
+            AutopilotModeKind::Terrain => self.autopilot_terrain_form.is_valid(),
+        };
+        if !valid {
+            return None;
         }
-    }
 
-    pub fn handle_client_message(&mut self, _: &BridgeSink, _: &ClientBoundMessage) {
-        // Nothing to listen for yet
+        Some(match self.mode {
+            AutopilotModeKind::Off => AutopilotMode::Off,
+            AutopilotModeKind::Hold => AutopilotMode::Hold {
+                roll: self.autopilot_hold_form.state.roll,
+                pitch: self.autopilot_hold_form.state.pitch,
+            },
+            AutopilotModeKind::Altitude => AutopilotMode::AltitudeHold {
+                roll: self.autopilot_altitude_form.state.roll,
+                altitude: self.autopilot_altitude_form.state.altitude,
+            },
+            AutopilotModeKind::Heading => AutopilotMode::Heading {
+                heading: self.autopilot_heading_form.state.heading,
+                bank_limit: self.autopilot_heading_form.state.bank_limit,
+                altitude: self.autopilot_heading_form.state.altitude,
+            },
+            AutopilotModeKind::VerticalSpeed => AutopilotMode::VerticalSpeed {
+                fpm: self.autopilot_vertical_speed_form.state.fpm,
+                altitude: self.autopilot_vertical_speed_form.state.altitude,
+            },
+            AutopilotModeKind::Lnav => AutopilotMode::Lnav {
+                waypoints: self.autopilot_lnav_form.state.waypoints.clone(),
+                bank_limit: self.autopilot_lnav_form.state.bank_limit,
+                altitude: self.autopilot_lnav_form.state.altitude,
+            },
+            AutopilotModeKind::Approach => AutopilotMode::Approach {
+                heading: self.autopilot_approach_form.state.heading,
+                course: self.autopilot_approach_form.state.course,
+                bank_limit: self.autopilot_approach_form.state.bank_limit,
+                altitude: self.autopilot_approach_form.state.altitude,
+                flare_height: self.autopilot_approach_form.state.flare_height,
+            },
+            AutopilotModeKind::Takeoff => AutopilotMode::Takeoff {
+                heading: self.autopilot_takeoff_form.state.heading,
+                rotate_speed: self.autopilot_takeoff_form.state.rotate_speed,
+                rotate_pitch: self.autopilot_takeoff_form.state.rotate_pitch,
+                altitude: self.autopilot_takeoff_form.state.altitude,
+            },
+            AutopilotModeKind::Orbit => self.autopilot_orbit_form.state.mode()?,
+            AutopilotModeKind::Holding => self.autopilot_holding_form.state.mode()?,
+            AutopilotModeKind::Glide => AutopilotMode::Glide {
+                bank_limit: self.autopilot_glide_form.state.bank_limit,
+            },
+            AutopilotModeKind::GoAround => AutopilotMode::GoAround {
+                heading: self.autopilot_go_around_form.state.heading,
+                altitude: self.autopilot_go_around_form.state.altitude,
+            },
+            AutopilotModeKind::Terrain => AutopilotMode::TerrainFollow {
+                heading: self.autopilot_terrain_form.state.heading,
+                bank_limit: self.autopilot_terrain_form.state.bank_limit,
+                clearance: self.autopilot_terrain_form.state.clearance,
+            },
+        })
     }
 
-    pub fn handle_user_event(&mut self, bridge: &BridgeSink, event: Event) {
-        match &event {
-            Event::Key(key) => {
-                match key.code {
-                    KeyCode::Char('1') if event_utils::is_nav_event(&event) => {
-                        self.mode = AutopilotModeKind::Off;
-                        self.active_mode = AutopilotModeKind::Off;
-                        bridge.broadcast(ClientBoundMessage::SetAutopilotMode(AutopilotMode::Off));
-                    }
-                    KeyCode::Char('2') if event_utils::is_nav_event(&event) => {
-                        self.mode = AutopilotModeKind::Hold;
-                    }
-                    KeyCode::Enter if event_utils::is_nav_event(&event) => {
-                        let active_form_valid = match self.mode {
-                            AutopilotModeKind::Off => true,
-                            AutopilotModeKind::Hold => self.autopilot_hold_form.is_valid(),
-                        };
-
-                        if active_form_valid && self.active_mode != self.mode {
-                            self.active_mode = self.mode;
-                            bridge.broadcast(ClientBoundMessage::SetAutopilotMode(
-                                match self.mode {
-                                    AutopilotModeKind::Off => AutopilotMode::Off,
-                                    AutopilotModeKind::Hold => AutopilotMode::Hold {
-                                        roll: self.autopilot_hold_form.state.roll,
-                                        pitch: self.autopilot_hold_form.state.pitch,
-                                    },
-                                },
-                            ));
-                        } else {
-                            self.active_mode = AutopilotModeKind::Off;
-                            bridge.broadcast(ClientBoundMessage::SetAutopilotMode(
-                                AutopilotMode::Off,
-                            ));
-                        }
-                    }
-                    _ => {
-                        match self.mode {
-                            AutopilotModeKind::Hold => {
-                                self.autopilot_hold_form.handle_user_event(event);
-                            }
-                            _ => {}
-                        };
-                    }
-                };
-            }
-            _ => {}
-        }
+    fn broadcast_autothrottle_mode(&self, bridge: &BridgeSink) {
+        bridge.broadcast(ClientBoundMessage::SetAutothrottleMode(
+            if self.autothrottle_active {
+                AutothrottleMode::Speed {
+                    knots: self.autothrottle_form.state.knots,
+                }
+            } else {
+                AutothrottleMode::Off
+            },
+        ));
     }
 
     pub fn draw(&self, rect: Rect, frame: &mut Frame, effects: &mut Effects, is_focused: bool) {
         let rect = rect.inner(Margin::new(1, 1));
 
-        let tabs = Tabs::new(vec![" OFF [1] ", " HOLD [2] "])
-            .highlight_style({
-                let style = Style::default().fg(Color::White);
-                match self.mode {
-                    AutopilotModeKind::Off => style.bg(Color::Red),
-                    AutopilotModeKind::Hold => {
-                        if self.active_mode == self.mode {
-                            style.bg(Color::Green)
-                        } else {
-                            style.bg(Color::Yellow)
-                        }
+        let tabs = Tabs::new(vec![
+            "OFF", "HOLD", "ALT", "HDG", "VS", "LNAV", "APPR", "TO", "ORBIT", "HLDG", "GLIDE",
+            "G/A", "TERR",
+        ])
+        .highlight_style({
+            let style = Style::default().fg(Color::White);
+            match self.mode {
+                AutopilotModeKind::Off => style.bg(Color::Red),
+                AutopilotModeKind::Hold
+                | AutopilotModeKind::Altitude
+                | AutopilotModeKind::Heading
+                | AutopilotModeKind::VerticalSpeed
+                | AutopilotModeKind::Lnav
+                | AutopilotModeKind::Approach
+                | AutopilotModeKind::Takeoff
+                | AutopilotModeKind::Orbit
+                | AutopilotModeKind::Holding
+                | AutopilotModeKind::Glide
+                | AutopilotModeKind::GoAround
+                | AutopilotModeKind::Terrain => {
+                    if self.active_mode == self.mode {
+                        style.bg(Color::Green)
+                    } else {
+                        style.bg(Color::Yellow)
                     }
                 }
-            })
-            .dark_gray()
-            .select(match self.mode {
-                AutopilotModeKind::Off => 0,
-                AutopilotModeKind::Hold => 1,
-            })
-            .divider("");
+            }
+        })
+        .dark_gray()
+        .select(match self.mode {
+            AutopilotModeKind::Off => 0,
+            AutopilotModeKind::Hold => 1,
+            AutopilotModeKind::Altitude => 2,
+            AutopilotModeKind::Heading => 3,
+            AutopilotModeKind::VerticalSpeed => 4,
+            AutopilotModeKind::Lnav => 5,
+            AutopilotModeKind::Approach => 6,
+            AutopilotModeKind::Takeoff => 7,
+            AutopilotModeKind::Orbit => 8,
+            AutopilotModeKind::Holding => 9,
+            AutopilotModeKind::Glide => 10,
+            AutopilotModeKind::GoAround => 11,
+            AutopilotModeKind::Terrain => 12,
+        })
+        .divider("");
 
         frame.render_widget(tabs, rect);
 
         let rect = rect
-            .clone()
             .inner(Margin::new(1, 0))
             .intersection(rect.offset(Offset { x: 0, y: 1 }));
 
         let block = Block::default().borders(Borders::TOP).dark_gray();
         frame.render_widget(block, rect);
 
-        let rect = rect
-            .clone()
-            .intersection(rect.offset(Offset { x: 0, y: 2 }));
+        let rect = rect.intersection(rect.offset(Offset { x: 0, y: 2 }));
 
         let layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(2),
+                Constraint::Length(2),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
             .split(rect);
 
+        let is_form_focused = is_focused && !self.autothrottle_in_focus;
+
         match &self.mode {
             AutopilotModeKind::Off => {
                 // No form to render
             }
             AutopilotModeKind::Hold => {
                 let (cursor_x, cursor_y) = self.autopilot_hold_form.draw(layout[0], frame);
-                if is_focused {
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::Altitude => {
+                let (cursor_x, cursor_y) = self.autopilot_altitude_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::Heading => {
+                let (cursor_x, cursor_y) = self.autopilot_heading_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::VerticalSpeed => {
+                let (cursor_x, cursor_y) =
+                    self.autopilot_vertical_speed_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::Lnav => {
+                let (cursor_x, cursor_y) = self.autopilot_lnav_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::Takeoff => {
+                let (cursor_x, cursor_y) = self.autopilot_takeoff_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::GoAround => {
+                let (cursor_x, cursor_y) = self.autopilot_go_around_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::Orbit => {
+                let (cursor_x, cursor_y) = self.autopilot_orbit_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::Holding => {
+                let (cursor_x, cursor_y) = self.autopilot_holding_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::Glide => {
+                let layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(2), Constraint::Min(0)])
+                    .split(layout[0]);
+
+                let (cursor_x, cursor_y) = self.autopilot_glide_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+
+                // Range over the terrain and how the airport being glided for stands
+                if let Some(glide) = &self.glide {
+                    let range = glide
+                        .range
+                        .map(|range| format!("RANGE {:.1} NM", range))
+                        .unwrap_or_else(|| "RANGE ---".to_string());
+                    let airport = match &glide.airport {
+                        Some(airport) => ratatui::text::Line::from(
+                            Span::raw(format!(
+                                "{} {:.1} NM  MARGIN {:+.1} NM",
+                                airport.icao, airport.distance, airport.margin
+                            ))
+                            .fg(if airport.margin >= 0.0 {
+                                Color::Green
+                            } else {
+                                Color::Red
+                            }),
+                        ),
+                        None => ratatui::text::Line::from("NO AIRPORT".red()),
+                    };
+
+                    frame.render_widget(
+                        Paragraph::new(vec![ratatui::text::Line::from(range), airport]),
+                        layout[1],
+                    );
+                }
+            }
+            AutopilotModeKind::Approach => {
+                let (cursor_x, cursor_y) = self.autopilot_approach_form.draw(layout[0], frame);
+                if is_form_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+            }
+            AutopilotModeKind::Terrain => {
+                let layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(4), Constraint::Min(0)])
+                    .split(layout[0]);
+
+                let (cursor_x, cursor_y) = self.autopilot_terrain_form.draw(layout[0], frame);
+                if is_form_focused {
                     effects.set_cursor_position(cursor_x, cursor_y);
                 }
+
+                // Height over the terrain below, and the least clearance along the track
+                if let Some(terrain) = &self.terrain {
+                    let clearance = self.autopilot_terrain_form.state.clearance;
+                    let ahead = match &terrain.ahead {
+                        Some(ahead) => ratatui::text::Line::from(
+                            Span::raw(format!(
+                                "AHEAD {:.0} FT AT {:.2} NM",
+                                ahead.clearance, ahead.distance
+                            ))
+                            .fg(if ahead.clearance >= clearance {
+                                Color::Green
+                            } else {
+                                Color::Red
+                            }),
+                        ),
+                        None => ratatui::text::Line::from("AHEAD ---"),
+                    };
+
+                    frame.render_widget(
+                        Paragraph::new(vec![
+                            ratatui::text::Line::from(format!("HEIGHT {:.0} FT", terrain.height)),
+                            ahead,
+                        ]),
+                        layout[1],
+                    );
+                }
+            }
+        }
+
+        // Flight mode annunciator: thrust, vertical and lateral channels, armed modes below,
+        // unless a recovery has taken over from all of them
+        if let Some(recovery) = self.status.recovery {
+            frame.render_widget(
+                Paragraph::new(vec![
+                    ratatui::text::Line::from(
+                        format!("RECOVERY: {}", upset_label(recovery.upset))
+                            .red()
+                            .bold(),
+                    ),
+                    ratatui::text::Line::from(recovery_phase_label(recovery.phase).red()),
+                ])
+                .centered(),
+                layout[1],
+            );
+        } else {
+            let layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Ratio(1, 3); 3])
+                .split(layout[1]);
+
+            // Envelope protection overriding the mode shows in place of an armed thrust mode
+            let protection = self.status.protection;
+            let protection = [
+                (protection.bank, "BANK"),
+                (protection.pitch, "PITCH"),
+                (protection.load_factor, "G"),
+            ]
+            .into_iter()
+            .filter_map(|(active, label)| active.then_some(label))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+            let columns = [
+                (
+                    Span::raw(self.status.thrust.map(thrust_label).unwrap_or("")),
+                    Span::raw(protection).red().bold(),
+                ),
+                (
+                    Span::raw(self.status.vertical.map(vertical_label).unwrap_or("")),
+                    Span::raw(self.status.vertical_armed.map(vertical_label).unwrap_or("")).cyan(),
+                ),
+                (
+                    Span::raw(self.status.lateral.map(lateral_label).unwrap_or("")),
+                    Span::raw(self.status.lateral_armed.map(lateral_label).unwrap_or("")).cyan(),
+                ),
+            ];
+
+            for ((active, armed), rect) in columns.into_iter().zip(layout.iter()) {
+                frame.render_widget(
+                    Paragraph::new(vec![
+                        ratatui::text::Line::from(active.green().bold()),
+                        ratatui::text::Line::from(armed),
+                    ])
+                    .centered(),
+                    *rect,
+                );
+            }
+        }
+
+        // Autothrottle runs alongside whichever mode is selected, as do the flight director and
+        // the upset protection
+        {
+            let layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Length(10),
+                    Constraint::Min(0),
+                    Constraint::Length(9),
+                    Constraint::Length(11),
+                ])
+                .split(layout[2]);
+
+            frame.render_widget(
+                Paragraph::new(
+                    Span::raw(" A/T [t] ")
+                        .fg(Color::White)
+                        .bg(if self.autothrottle_active {
+                            Color::Green
+                        } else {
+                            Color::DarkGray
+                        })
+                        .bold(),
+                ),
+                layout[0],
+            );
+
+            let (cursor_x, cursor_y) = self.autothrottle_form.draw(layout[1], frame);
+            if is_focused && self.autothrottle_in_focus {
+                effects.set_cursor_position(cursor_x, cursor_y);
             }
+
+            frame.render_widget(
+                Paragraph::new(
+                    Span::raw(" F/D [f] ")
+                        .fg(Color::White)
+                        .bg(if self.status.flight_director {
+                            Color::Magenta
+                        } else {
+                            Color::DarkGray
+                        })
+                        .bold(),
+                ),
+                layout[2],
+            );
+
+            frame.render_widget(
+                Paragraph::new(
+                    Span::raw(" PROT [o] ")
+                        .fg(Color::White)
+                        .bg(if self.status.upset_protection {
+                            Color::Green
+                        } else {
+                            Color::DarkGray
+                        })
+                        .bold(),
+                ),
+                layout[3].offset(Offset { x: 1, y: 0 }),
+            );
+        }
+
+        if let Some(profile) = &self.profile {
+            frame.render_widget(
+                Paragraph::new(ratatui::text::Line::from(vec![
+                    Span::raw(" PROFILE [p] ")
+                        .fg(Color::White)
+                        .on_dark_gray()
+                        .bold(),
+                    Span::raw(format!(
+                        " {}{}  VR {:.0}  VY {:.0}  VAPP {:.0}  VG {:.0}",
+                        profile.active,
+                        if profile.automatic { " (AUTO)" } else { "" },
+                        profile.speeds.rotate,
+                        profile.speeds.climb,
+                        profile.speeds.approach,
+                        profile.speeds.glide,
+                    )),
+                ])),
+                layout[3],
+            );
         }
 
-        if self.mode != AutopilotModeKind::Off {
+        if let Some(alert) = self.alert {
+            frame.render_widget(
+                Paragraph::new(
+                    Span::raw(match alert {
+                        AutopilotAlert::PilotOverride => " A/P DISC: PILOT OVERRIDE ".to_string(),
+                        AutopilotAlert::ThrottleOverride => {
+                            " A/T DISC: THROTTLE OVERRIDE ".to_string()
+                        }
+                        AutopilotAlert::ConnectionLost => " A/P DISC: NO CONNECTION ".to_string(),
+                        AutopilotAlert::TelemetryLost(TelemetryInput::Airspeed) => {
+                            " A/T DISC: NO AIRSPEED ".to_string()
+                        }
+                        AutopilotAlert::TelemetryLost(input) => {
+                            format!(" A/P DISC: NO {} ", telemetry_input_label(input))
+                        }
+                        AutopilotAlert::Degraded(input) => {
+                            format!(" A/P DEGRADED: NO {} ", telemetry_input_label(input))
+                        }
+                        AutopilotAlert::Upset(upset) => {
+                            format!(" UPSET RECOVERY: {} ", upset_label(upset))
+                        }
+                        AutopilotAlert::UnstableApproach => {
+                            " GO AROUND: UNSTABLE APPROACH ".to_string()
+                        }
+                    })
+                    .white()
+                    .bg(match alert {
+                        AutopilotAlert::Degraded(_) => Color::Yellow,
+                        _ => Color::Red,
+                    })
+                    .bold(),
+                )
+                .centered(),
+                layout[4],
+            );
+        } else if self.mode != AutopilotModeKind::Off {
             frame.render_widget(
                 Paragraph::new(
                     Span::raw(" ACTIVE [<ENTER>] ")
@@ -193,185 +1344,281 @@ impl AutopilotView {
                         .bold(),
                 )
                 .centered(),
-                layout[1],
+                layout[4],
             );
         }
     }
 }
 
+// Automatic, then each profile in turn, then back to automatic
+fn next_profile_selection(profile: &ProfileStatus) -> ProfileSelection {
+    if profile.automatic {
+        return profile
+            .available
+            .first()
+            .cloned()
+            .map(ProfileSelection::Named)
+            .unwrap_or(ProfileSelection::Automatic);
+    }
+
+    profile
+        .available
+        .iter()
+        .position(|name| name == &profile.active)
+        .and_then(|index| profile.available.get(index + 1))
+        .cloned()
+        .map(ProfileSelection::Named)
+        .unwrap_or(ProfileSelection::Automatic)
+}
+
+// A "lat,lon" pair in decimal degrees
+fn parse_waypoint(pair: &str) -> Option<Waypoint> {
+    let (latitude, longitude) = pair.split_once(',')?;
+    let latitude = latitude.trim().parse::<f64>().ok()?;
+    let longitude = longitude.trim().parse::<f64>().ok()?;
+    if (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude) {
+        Some(Waypoint {
+            latitude,
+            longitude,
+        })
+    } else {
+        None
+    }
+}
+
+fn parse_turn_direction(value: &str) -> Option<TurnDirection> {
+    match value.to_ascii_uppercase().as_str() {
+        "L" => Some(TurnDirection::Left),
+        "R" => Some(TurnDirection::Right),
+        _ => None,
+    }
+}
+
+fn turn_direction_label(direction: TurnDirection) -> &'static str {
+    match direction {
+        TurnDirection::Left => "L",
+        TurnDirection::Right => "R",
+    }
+}
+
+fn telemetry_input_label(input: TelemetryInput) -> &'static str {
+    match input {
+        TelemetryInput::Attitude => "ATTITUDE",
+        TelemetryInput::Position => "POSITION",
+        TelemetryInput::Airspeed => "AIRSPEED",
+        TelemetryInput::Nav1 => "NAV1",
+        TelemetryInput::Terrain => "TERRAIN",
+        TelemetryInput::TerrainAhead => "TERRAIN AHEAD",
+    }
+}
+
+fn upset_label(upset: Upset) -> &'static str {
+    match upset {
+        Upset::Stall => "STALL",
+        Upset::NoseHigh => "NOSE HIGH",
+        Upset::NoseLow => "NOSE LOW",
+        Upset::Bank => "BANK",
+        Upset::Requested => "LEVEL",
+    }
+}
+
+fn recovery_phase_label(phase: RecoveryPhase) -> &'static str {
+    match phase {
+        RecoveryPhase::Unload => "UNLOAD",
+        RecoveryPhase::RollLevel => "ROLL LEVEL",
+        RecoveryPhase::Pitch => "PITCH",
+    }
+}
+
+fn thrust_label(mode: ThrustMode) -> &'static str {
+    match mode {
+        ThrustMode::Speed => "SPEED",
+        ThrustMode::Takeoff => "TO",
+        ThrustMode::Retard => "RETARD",
+        ThrustMode::GoAround => "TOGA",
+    }
+}
+
+fn vertical_label(mode: VerticalMode) -> &'static str {
+    match mode {
+        VerticalMode::Pitch => "PITCH",
+        VerticalMode::VerticalSpeed => "VS",
+        VerticalMode::AltitudeCapture => "ALT*",
+        VerticalMode::AltitudeHold => "ALT",
+        VerticalMode::Glideslope => "G/S",
+        VerticalMode::Flare => "FLARE",
+        VerticalMode::Takeoff => "TO",
+        VerticalMode::Glide => "GLIDE",
+        VerticalMode::GoAround => "GA",
+        VerticalMode::TerrainFollow => "TERR",
+    }
+}
+
+fn lateral_label(mode: LateralMode) -> &'static str {
+    match mode {
+        LateralMode::Roll => "ROLL",
+        LateralMode::Heading => "HDG",
+        LateralMode::Lnav => "LNAV",
+        LateralMode::Localizer => "LOC",
+        LateralMode::Runway => "RWY",
+        LateralMode::Rollout => "ROLLOUT",
+        LateralMode::Orbit => "ORBIT",
+        LateralMode::Hold => "HOLD",
+        LateralMode::Direct => "DCT",
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum AutopilotModeKind {
     Off,
     Hold,
+    Altitude,
+    Heading,
+    VerticalSpeed,
+    Lnav,
+    Approach,
+    Takeoff,
+    Orbit,
+    Holding,
+    Glide,
+    GoAround,
+    Terrain,
+}
+
+impl From<&AutopilotMode> for AutopilotModeKind {
+    fn from(mode: &AutopilotMode) -> Self {
+        match mode {
+            AutopilotMode::Off => AutopilotModeKind::Off,
+            AutopilotMode::Hold { .. } => AutopilotModeKind::Hold,
+            AutopilotMode::AltitudeHold { .. } => AutopilotModeKind::Altitude,
+            AutopilotMode::Heading { .. } => AutopilotModeKind::Heading,
+            AutopilotMode::VerticalSpeed { .. } => AutopilotModeKind::VerticalSpeed,
+            AutopilotMode::Lnav { .. } => AutopilotModeKind::Lnav,
+            AutopilotMode::Approach { .. } => AutopilotModeKind::Approach,
+            AutopilotMode::Takeoff { .. } => AutopilotModeKind::Takeoff,
+            AutopilotMode::Orbit { .. } => AutopilotModeKind::Orbit,
+            AutopilotMode::Holding { .. } => AutopilotModeKind::Holding,
+            AutopilotMode::Glide { .. } => AutopilotModeKind::Glide,
+            AutopilotMode::GoAround { .. } => AutopilotModeKind::GoAround,
+            AutopilotMode::TerrainFollow { .. } => AutopilotModeKind::Terrain,
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 struct AutopilotHoldState {
     roll: f64,
     pitch: f64,
-    altitude: f64,
 }
 
-struct Form<S> {
-    state: S,
-    fields: Vec<Field<S>>,
-    active_field_index: usize,
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutopilotAltitudeState {
+    roll: f64,
+    altitude: f64,
 }
 
-impl<S> Form<S> {
-    fn new(state: S, fields: Vec<Field<S>>) -> Self {
-        Form {
-            state,
-            fields,
-            active_field_index: 0,
-        }
-    }
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutopilotHeadingState {
+    heading: f64,
+    bank_limit: f64,
+    altitude: f64,
+}
 
-    fn is_valid(&self) -> bool {
-        self.fields.iter().all(|field| field.is_valid)
-    }
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutopilotApproachState {
+    heading: f64,
+    course: f64,
+    bank_limit: f64,
+    altitude: f64,
+    flare_height: Option<f64>,
+}
 
-    fn handle_user_event(&mut self, event: Event) {
-        match &event {
-            Event::Key(key) => match key.code {
-                KeyCode::Up | KeyCode::BackTab => {
-                    if self.active_field_index > 0 {
-                        self.active_field_index -= 1;
-                    } else {
-                        self.active_field_index = self.fields.len() - 1;
-                    }
-                }
-                KeyCode::Down | KeyCode::Tab => {
-                    if self.active_field_index < self.fields.len() - 1 {
-                        self.active_field_index += 1;
-                    } else {
-                        self.active_field_index = 0;
-                    }
-                }
-                _ => {
-                    self.fields[self.active_field_index].handle_user_event(event, &mut self.state);
-                }
-            },
-            _ => {}
-        }
-    }
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutopilotTakeoffState {
+    heading: f64,
+    rotate_speed: f64,
+    rotate_pitch: f64,
+    altitude: f64,
+}
 
-    fn draw(&self, mut rect: Rect, frame: &mut Frame) -> (u16, u16) {
-        let label_width = self
-            .fields
-            .iter()
-            .map(|field| field.label.len())
-            .max()
-            .unwrap_or(0) as u16;
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutopilotGoAroundState {
+    heading: Option<f64>,
+    altitude: f64,
+}
 
-        let form_area = rect;
-        let value_width = rect.width - label_width - 4;
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutopilotVerticalSpeedState {
+    fpm: f64,
+    altitude: Option<f64>,
+}
 
-        for field in self.fields.iter() {
-            let layout = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Length(label_width),
-                    Constraint::Min(0),
-                    Constraint::Length(value_width),
-                ])
-                .split(Rect {
-                    x: rect.x,
-                    y: rect.y,
-                    width: rect.width,
-                    height: 1,
-                });
+#[derive(Clone, PartialEq, Debug)]
+struct AutopilotLnavState {
+    waypoints: Vec<Waypoint>,
+    bank_limit: f64,
+    altitude: f64,
+}
 
-            frame.render_widget(
-                Paragraph::new(field.label.clone()).white().bold(),
-                layout[0],
-            );
+#[derive(Clone, PartialEq, Debug)]
+struct AutopilotOrbitState {
+    center: Option<Waypoint>,
+    radius: f64,
+    direction: TurnDirection,
+    bank_limit: f64,
+    altitude: f64,
+}
 
-            frame.render_widget(
-                Paragraph::new(field.value.clone())
-                    .bg(if field.is_valid {
-                        Color::Black
-                    } else {
-                        Color::Red
-                    })
-                    .white(),
-                layout[2],
-            );
+impl AutopilotOrbitState {
+    // None until a center has been entered
+    fn mode(&self) -> Option<AutopilotMode> {
+        Some(AutopilotMode::Orbit {
+            center: self.center.clone()?,
+            radius: self.radius,
+            direction: self.direction,
+            bank_limit: self.bank_limit,
+            altitude: self.altitude,
+        })
+    }
+}
 
-            rect = rect
-                .clone()
-                .intersection(rect.offset(Offset { x: 0, y: 2 }));
-        }
+#[derive(Clone, PartialEq, Debug)]
+struct AutopilotHoldingState {
+    fix: Option<Waypoint>,
+    inbound_course: f64,
+    leg: HoldLeg,
+    direction: TurnDirection,
+    bank_limit: f64,
+    altitude: f64,
+}
 
-        (
-            form_area.x + form_area.width - value_width
-                + self.fields[self.active_field_index].cursor_position as u16,
-            form_area.y + self.active_field_index as u16 * 2,
-        )
+impl AutopilotHoldingState {
+    // None until a fix has been entered
+    fn mode(&self) -> Option<AutopilotMode> {
+        Some(AutopilotMode::Holding {
+            fix: self.fix.clone()?,
+            inbound_course: self.inbound_course,
+            leg: self.leg,
+            direction: self.direction,
+            bank_limit: self.bank_limit,
+            altitude: self.altitude,
+        })
     }
 }
 
-struct Field<S> {
-    label: String,
-    value: String,
-    is_valid: bool,
-    on_edit: fn(&mut String, &mut S) -> bool,
-    cursor_position: usize,
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutopilotTerrainState {
+    heading: f64,
+    bank_limit: f64,
+    clearance: f64,
 }
 
-impl<S> Field<S> {
-    fn new(
-        label: impl Into<String>,
-        value: impl Into<String>,
-        on_edit: fn(&mut String, &mut S) -> bool,
-    ) -> Self {
-        let label = label.into();
-        let value = value.into();
-        let value_len = value.len();
-
-        Field {
-            label,
-            value,
-            on_edit,
-            is_valid: true,
-            cursor_position: value_len,
-        }
-    }
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutopilotGlideState {
+    bank_limit: f64,
+}
 
-    fn handle_user_event(&mut self, event: Event, state: &mut S) {
-        match &event {
-            Event::Key(key) => match key.code {
-                KeyCode::Char(c) => {
-                    self.value.insert(self.cursor_position, c);
-                    self.cursor_position += 1;
-                    self.is_valid = (self.on_edit)(&mut self.value, state);
-                }
-                KeyCode::Backspace => {
-                    if self.cursor_position > 0 {
-                        self.value.remove(self.cursor_position - 1);
-                        self.cursor_position -= 1;
-                        self.is_valid = (self.on_edit)(&mut self.value, state);
-                    }
-                }
-                KeyCode::Delete => {
-                    if self.cursor_position < self.value.len() {
-                        self.value.remove(self.cursor_position);
-                        self.is_valid = (self.on_edit)(&mut self.value, state);
-                    }
-                }
-                KeyCode::Left => {
-                    if self.cursor_position > 0 {
-                        self.cursor_position -= 1;
-                    }
-                }
-                KeyCode::Right => {
-                    if self.cursor_position < self.value.len() {
-                        self.cursor_position += 1;
-                    }
-                }
-                _ => {}
-            },
-            _ => {}
-        }
-    }
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct AutothrottleState {
+    knots: f64,
 }
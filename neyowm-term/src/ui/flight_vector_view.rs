@@ -1,4 +1,4 @@
-use neyowm::{ClientBoundMessage, TelemetryUpdate};
+use neyowm::{ClientBoundMessage, FlightDirectorCue, TelemetryUpdate};
 use ratatui::{
     layout::Rect,
     style::Color,
@@ -11,6 +11,7 @@ use crate::app::BridgeSink;
 pub struct FlightVectorView {
     pub tilt: f64,
     pub attitude: f64,
+    pub cue: Option<FlightDirectorCue>,
 }
 
 impl FlightVectorView {
@@ -18,6 +19,7 @@ impl FlightVectorView {
         FlightVectorView {
             tilt: 0.0,
             attitude: 0.0,
+            cue: None,
         }
     }
 
@@ -31,6 +33,9 @@ impl FlightVectorView {
                 self.tilt = *roll;
                 self.attitude = *pitch;
             }
+            ClientBoundMessage::FlightDirector(cue) => {
+                self.cue = *cue;
+            }
             _ => {}
         }
     }
@@ -112,5 +117,38 @@ impl FlightVectorView {
             });
 
         frame.render_widget(canvas, rect);
+
+        // Command bars at the attitude the modes ask for: flying the crosshair onto where
+        // they cross follows the cues
+        if let Some(cue) = self.cue {
+            const BAR_LENGTH: f64 = 0.3;
+            let command = (
+                f64::clamp(cue.roll / 45.0, -1.0, 1.0),
+                f64::clamp(cue.pitch / 45.0, -1.0, 1.0),
+            );
+
+            let canvas = Canvas::default()
+                .x_bounds([-1.0, 1.0])
+                .y_bounds([-1.0, 1.0])
+                .paint(|ctx| {
+                    ctx.draw(&Line {
+                        x1: command.0,
+                        y1: command.1 - BAR_LENGTH,
+                        x2: command.0,
+                        y2: command.1 + BAR_LENGTH,
+                        color: Color::Magenta,
+                    });
+
+                    ctx.draw(&Line {
+                        x1: command.0 - BAR_LENGTH,
+                        y1: command.1,
+                        x2: command.0 + BAR_LENGTH,
+                        y2: command.1,
+                        color: Color::Magenta,
+                    });
+                });
+
+            frame.render_widget(canvas, rect);
+        }
     }
 }
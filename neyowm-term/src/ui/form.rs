@@ -0,0 +1,166 @@
+use crossterm::event::{Event, KeyCode};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Offset, Rect},
+    style::{Color, Stylize},
+    widgets::Paragraph,
+    Frame,
+};
+
+pub struct Form<S> {
+    pub state: S,
+    fields: Vec<Field<S>>,
+    active_field_index: usize,
+}
+
+impl<S> Form<S> {
+    pub fn new(state: S, fields: Vec<Field<S>>) -> Self {
+        Form {
+            state,
+            fields,
+            active_field_index: 0,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.fields.iter().all(|field| field.is_valid)
+    }
+
+    pub fn handle_user_event(&mut self, event: Event) {
+        if let Event::Key(key) = &event {
+            match key.code {
+                KeyCode::Up | KeyCode::BackTab => {
+                    if self.active_field_index > 0 {
+                        self.active_field_index -= 1;
+                    } else {
+                        self.active_field_index = self.fields.len() - 1;
+                    }
+                }
+                KeyCode::Down | KeyCode::Tab => {
+                    if self.active_field_index < self.fields.len() - 1 {
+                        self.active_field_index += 1;
+                    } else {
+                        self.active_field_index = 0;
+                    }
+                }
+                _ => {
+                    self.fields[self.active_field_index].handle_user_event(event, &mut self.state);
+                }
+            }
+        }
+    }
+
+    pub fn draw(&self, mut rect: Rect, frame: &mut Frame) -> (u16, u16) {
+        let label_width = self
+            .fields
+            .iter()
+            .map(|field| field.label.len())
+            .max()
+            .unwrap_or(0) as u16;
+
+        let form_area = rect;
+        let value_width = rect.width - label_width - 4;
+
+        for field in self.fields.iter() {
+            let layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Length(label_width),
+                    Constraint::Min(0),
+                    Constraint::Length(value_width),
+                ])
+                .split(Rect {
+                    x: rect.x,
+                    y: rect.y,
+                    width: rect.width,
+                    height: 1,
+                });
+
+            frame.render_widget(
+                Paragraph::new(field.label.clone()).white().bold(),
+                layout[0],
+            );
+
+            frame.render_widget(
+                Paragraph::new(field.value.clone())
+                    .scroll((0, field.scroll_offset(value_width)))
+                    .bg(if field.is_valid {
+                        Color::Black
+                    } else {
+                        Color::Red
+                    })
+                    .white(),
+                layout[2],
+            );
+
+            rect = rect.intersection(rect.offset(Offset { x: 0, y: 2 }));
+        }
+
+        let active_field = &self.fields[self.active_field_index];
+        (
+            form_area.x + form_area.width - value_width + active_field.cursor_position as u16
+                - active_field.scroll_offset(value_width),
+            form_area.y + self.active_field_index as u16 * 2,
+        )
+    }
+}
+
+pub struct Field<S> {
+    label: String,
+    value: String,
+    is_valid: bool,
+    on_edit: fn(&mut String, &mut S) -> bool,
+    cursor_position: usize,
+}
+
+impl<S> Field<S> {
+    pub fn new(
+        label: impl Into<String>,
+        value: impl Into<String>,
+        on_edit: fn(&mut String, &mut S) -> bool,
+    ) -> Self {
+        let label = label.into();
+        let value = value.into();
+        let value_len = value.len();
+
+        Field {
+            label,
+            value,
+            on_edit,
+            is_valid: true,
+            cursor_position: value_len,
+        }
+    }
+
+    // Scroll long values so the cursor stays within the visible width
+    fn scroll_offset(&self, width: u16) -> u16 {
+        (self.cursor_position as u16 + 1).saturating_sub(width)
+    }
+
+    fn handle_user_event(&mut self, event: Event, state: &mut S) {
+        if let Event::Key(key) = &event {
+            match key.code {
+                KeyCode::Char(c) => {
+                    self.value.insert(self.cursor_position, c);
+                    self.cursor_position += 1;
+                    self.is_valid = (self.on_edit)(&mut self.value, state);
+                }
+                KeyCode::Backspace if self.cursor_position > 0 => {
+                    self.value.remove(self.cursor_position - 1);
+                    self.cursor_position -= 1;
+                    self.is_valid = (self.on_edit)(&mut self.value, state);
+                }
+                KeyCode::Delete if self.cursor_position < self.value.len() => {
+                    self.value.remove(self.cursor_position);
+                    self.is_valid = (self.on_edit)(&mut self.value, state);
+                }
+                KeyCode::Left if self.cursor_position > 0 => {
+                    self.cursor_position -= 1;
+                }
+                KeyCode::Right if self.cursor_position < self.value.len() => {
+                    self.cursor_position += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+}
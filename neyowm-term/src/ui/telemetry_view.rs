@@ -22,6 +22,9 @@ pub struct TelemetryView {
 
     elevation_row: DisplayRow,
     velocity_row: DisplayRow,
+    airspeed_row: DisplayRow,
+    sideslip_row: DisplayRow,
+    load_factor_row: DisplayRow,
 
     aileron_row: DisplayRow,
     elevator_row: DisplayRow,
@@ -44,6 +47,9 @@ impl TelemetryView {
 
             elevation_row: DisplayRow::new("ELV"),
             velocity_row: DisplayRow::new("SPEED"),
+            airspeed_row: DisplayRow::new("IAS"),
+            sideslip_row: DisplayRow::new("BETA"),
+            load_factor_row: DisplayRow::new("G"),
 
             aileron_row: DisplayRow::new("AIL"),
             elevator_row: DisplayRow::new("ELVR"),
@@ -83,6 +89,15 @@ impl TelemetryView {
                 self.velocity_row
                     .update(velocity.0.abs() + velocity.1.abs() + velocity.2.abs());
             }
+            ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Airspeed { indicated }) => {
+                self.airspeed_row.update(*indicated);
+            }
+            ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Sideslip { beta }) => {
+                self.sideslip_row.update(*beta);
+            }
+            ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::LoadFactor { normal }) => {
+                self.load_factor_row.update(*normal);
+            }
             ClientBoundMessage::UpdateTelemetry(TelemetryUpdate::Control {
                 aileron,
                 elevator,
@@ -115,6 +130,9 @@ impl TelemetryView {
             Row::new([Cell::new(""); 0]),
             Row::new(self.elevation_row.cells()),
             Row::new(self.velocity_row.cells()),
+            Row::new(self.airspeed_row.cells()),
+            Row::new(self.sideslip_row.cells()),
+            Row::new(self.load_factor_row.cells()),
             Row::new([Cell::new(""); 0]),
             Row::new(self.aileron_row.cells()),
             Row::new(self.elevator_row.cells()),
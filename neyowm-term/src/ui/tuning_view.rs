@@ -0,0 +1,403 @@
+use std::collections::VecDeque;
+
+use crossterm::event::{Event, KeyCode};
+use neyowm::{
+    AutotuneFailure, AutotuneStatus, ClientBoundMessage, ControlAxis, ControllerInternals, Gains,
+    TuningRule,
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Margin, Offset, Rect},
+    style::{Color, Style, Stylize},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Tabs},
+    Frame,
+};
+
+use super::form::{Field, Form};
+use crate::app::{event_utils, BridgeSink, Effects};
+
+// Updates kept for the charts, about half a minute at the attitude telemetry rate
+const HISTORY_LENGTH: usize = 120;
+
+pub struct TuningView {
+    axis: ControlAxis,
+    roll: AxisTuning,
+    pitch: AxisTuning,
+    // Last report from an autotune, kept until a proposal is applied or rejected
+    autotune: Option<AutotuneStatus>,
+}
+
+impl TuningView {
+    pub fn new() -> Self {
+        TuningView {
+            axis: ControlAxis::Roll,
+            roll: AxisTuning::new(),
+            pitch: AxisTuning::new(),
+            autotune: None,
+        }
+    }
+
+    pub fn handle_client_message(&mut self, _: &BridgeSink, msg: &ClientBoundMessage) {
+        match msg {
+            ClientBoundMessage::ControllerInternals(internals) => {
+                let tuning = match internals.axis {
+                    ControlAxis::Roll => &mut self.roll,
+                    ControlAxis::Pitch => &mut self.pitch,
+                };
+
+                tuning.record(*internals);
+            }
+            ClientBoundMessage::AutotuneStatus(status) => {
+                self.autotune = Some(*status);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_user_event(&mut self, bridge: &BridgeSink, event: Event) {
+        let Event::Key(key) = &event else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Char('1') if event_utils::is_nav_event(&event) => {
+                self.axis = ControlAxis::Roll;
+            }
+            KeyCode::Char('2') if event_utils::is_nav_event(&event) => {
+                self.axis = ControlAxis::Pitch;
+            }
+            KeyCode::Char('z') if event_utils::is_nav_event(&event) => {
+                bridge.broadcast(ClientBoundMessage::StartAutotune(
+                    self.axis,
+                    TuningRule::ZieglerNichols,
+                ));
+            }
+            KeyCode::Char('t') if event_utils::is_nav_event(&event) => {
+                bridge.broadcast(ClientBoundMessage::StartAutotune(
+                    self.axis,
+                    TuningRule::TyreusLuyben,
+                ));
+            }
+            KeyCode::Char('y') if event_utils::is_nav_event(&event) => {
+                if let Some(AutotuneStatus::Proposed { axis, gains, .. }) = self.autotune {
+                    bridge.broadcast(ClientBoundMessage::SetGains(axis, gains));
+                    self.autotune = None;
+                }
+            }
+            KeyCode::Char('x') if event_utils::is_nav_event(&event) => match self.autotune {
+                Some(AutotuneStatus::Running { .. }) => {
+                    bridge.broadcast(ClientBoundMessage::StopAutotune);
+                }
+                _ => self.autotune = None,
+            },
+            KeyCode::Enter => {
+                let form = &self.tuning().form;
+                if form.is_valid() {
+                    bridge.broadcast(ClientBoundMessage::SetGains(self.axis, form.state));
+                }
+            }
+            _ => {
+                self.tuning_mut().form.handle_user_event(event);
+            }
+        }
+    }
+
+    fn tuning(&self) -> &AxisTuning {
+        match self.axis {
+            ControlAxis::Roll => &self.roll,
+            ControlAxis::Pitch => &self.pitch,
+        }
+    }
+
+    fn tuning_mut(&mut self) -> &mut AxisTuning {
+        match self.axis {
+            ControlAxis::Roll => &mut self.roll,
+            ControlAxis::Pitch => &mut self.pitch,
+        }
+    }
+
+    pub fn draw(&self, rect: Rect, frame: &mut Frame, effects: &mut Effects, is_focused: bool) {
+        let rect = rect.inner(Margin::new(1, 1));
+
+        let tabs = Tabs::new(vec!["ROLL", "PITCH"])
+            .highlight_style(Style::default().fg(Color::White).bg(Color::Blue))
+            .dark_gray()
+            .select(match self.axis {
+                ControlAxis::Roll => 0,
+                ControlAxis::Pitch => 1,
+            })
+            .divider("");
+
+        frame.render_widget(tabs, rect);
+
+        let rect = rect
+            .inner(Margin::new(1, 0))
+            .intersection(rect.offset(Offset { x: 0, y: 1 }));
+
+        let block = Block::default().borders(Borders::TOP).dark_gray();
+        frame.render_widget(block, rect);
+
+        let rect = rect.intersection(rect.offset(Offset { x: 0, y: 2 }));
+
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(28), Constraint::Min(0)])
+            .split(rect);
+
+        let tuning = self.tuning();
+
+        // Gains, applied with enter, above the autotune status
+        {
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Length(4),
+                ])
+                .split(layout[0].inner(Margin::new(1, 0)));
+
+            if tuning.reported_gains.is_some() {
+                let (cursor_x, cursor_y) = tuning.form.draw(layout[0], frame);
+                if is_focused {
+                    effects.set_cursor_position(cursor_x, cursor_y);
+                }
+
+                frame.render_widget(
+                    Paragraph::new(Span::raw(" APPLY [<ENTER>] ").white().on_black().bold()),
+                    layout[1],
+                );
+            } else {
+                frame.render_widget(Paragraph::new("NO GAINS").dark_gray().centered(), layout[0]);
+            }
+
+            frame.render_widget(
+                Paragraph::new(autotune_lines(self.autotune.as_ref())),
+                layout[3],
+            );
+        }
+
+        // Attitude against its target, and the terms making up the output
+        {
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Ratio(1, 2); 2])
+                .split(layout[1]);
+
+            let setpoint = tuning.series(|internals| Some(internals.setpoint));
+            let measurement = tuning.series(|internals| Some(internals.measurement));
+            draw_chart(
+                frame,
+                layout[0],
+                " ATTITUDE ",
+                vec![
+                    ("SET", Color::Yellow, &setpoint),
+                    ("ACT", Color::White, &measurement),
+                ],
+            );
+
+            let p = tuning.series(|internals| internals.terms.map(|terms| terms.p));
+            let i = tuning.series(|internals| internals.terms.map(|terms| terms.i));
+            let d = tuning.series(|internals| internals.terms.map(|terms| terms.d));
+            let output = tuning.series(|internals| Some(internals.output));
+            draw_chart(
+                frame,
+                layout[1],
+                " OUTPUT ",
+                vec![
+                    ("P", Color::Green, &p),
+                    ("I", Color::Cyan, &i),
+                    ("D", Color::Magenta, &d),
+                    ("OUT", Color::White, &output),
+                ],
+            );
+        }
+    }
+}
+
+fn autotune_lines(status: Option<&AutotuneStatus>) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::raw(" AUTOTUNE ").white().on_black().bold(),
+        Span::raw(" [z] ZN [t] TL").dark_gray(),
+    ])];
+
+    match status {
+        Some(AutotuneStatus::Running { axis, cycles }) => {
+            lines.push(Line::from(
+                format!("{} CYCLE {}", axis_name(*axis), cycles).yellow(),
+            ));
+            lines.push(Line::from("[x] STOP".dark_gray()));
+        }
+        Some(AutotuneStatus::Proposed {
+            axis,
+            ultimate_gain,
+            ultimate_period,
+            gains,
+        }) => {
+            lines.push(Line::from(
+                format!(
+                    "{} KU {:.3} TU {:.1}",
+                    axis_name(*axis),
+                    ultimate_gain,
+                    ultimate_period
+                )
+                .green(),
+            ));
+            lines.push(Line::from(
+                format!("KP {:.3} KI {:.3} KD {:.3}", gains.kp, gains.ki, gains.kd).green(),
+            ));
+            lines.push(Line::from("[y] APPLY [x] REJECT".dark_gray()));
+        }
+        Some(AutotuneStatus::Failed { axis, failure }) => {
+            let reason = match failure {
+                AutotuneFailure::NotEngaged => "NOT ENGAGED",
+                AutotuneFailure::Cancelled => "CANCELLED",
+                AutotuneFailure::Diverged => "DIVERGED",
+                AutotuneFailure::TimedOut => "TIMED OUT",
+                AutotuneFailure::NoOscillation => "NO OSCILLATION",
+            };
+            lines.push(Line::from(format!("{} {}", axis_name(*axis), reason).red()));
+        }
+        None => {}
+    }
+
+    lines
+}
+
+fn axis_name(axis: ControlAxis) -> &'static str {
+    match axis {
+        ControlAxis::Roll => "ROLL",
+        ControlAxis::Pitch => "PITCH",
+    }
+}
+
+// Name, colour and points of one line on a chart
+type Series<'a> = (&'a str, Color, &'a Vec<(f64, f64)>);
+
+fn draw_chart(frame: &mut Frame, rect: Rect, title: &str, series: Vec<Series>) {
+    let (min, max) = series
+        .iter()
+        .flat_map(|(_, _, points)| points.iter().map(|(_, y)| *y))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), y| {
+            (min.min(y), max.max(y))
+        });
+
+    // Keep a flat or empty series from collapsing the axis
+    let (min, max) = if min.is_finite() && max - min > 1e-3 {
+        (min, max)
+    } else if min.is_finite() {
+        (min - 1.0, max + 1.0)
+    } else {
+        (-1.0, 1.0)
+    };
+
+    let datasets = series
+        .into_iter()
+        .map(|(name, color, points)| {
+            Dataset::default()
+                .name(name)
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(color))
+                .data(points)
+        })
+        .collect();
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .title(title)
+                .dark_gray(),
+        )
+        .x_axis(Axis::default().bounds([0.0, HISTORY_LENGTH as f64]))
+        .y_axis(
+            Axis::default()
+                .bounds([min, max])
+                .labels(vec![
+                    Span::raw(format!("{:.2}", min)),
+                    Span::raw(format!("{:.2}", max)),
+                ])
+                .dark_gray(),
+        );
+
+    frame.render_widget(chart, rect);
+}
+
+struct AxisTuning {
+    form: Form<Gains>,
+    reported_gains: Option<Gains>,
+    history: VecDeque<ControllerInternals>,
+}
+
+impl AxisTuning {
+    fn new() -> Self {
+        AxisTuning {
+            form: gains_form(Gains {
+                kp: 0.0,
+                ki: 0.0,
+                kd: 0.0,
+            }),
+            reported_gains: None,
+            history: VecDeque::with_capacity(HISTORY_LENGTH),
+        }
+    }
+
+    fn record(&mut self, internals: ControllerInternals) {
+        if self.history.len() == HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+        self.history.push_back(internals);
+
+        // Only refill the form when the gains change under it, e.g. with a new profile, so
+        // edits in progress are kept
+        if internals.gains != self.reported_gains {
+            self.reported_gains = internals.gains;
+            if let Some(gains) = internals.gains {
+                self.form = gains_form(gains);
+            }
+        }
+    }
+
+    fn series(&self, value: impl Fn(&ControllerInternals) -> Option<f64>) -> Vec<(f64, f64)> {
+        self.history
+            .iter()
+            .enumerate()
+            .filter_map(|(index, internals)| Some((index as f64, value(internals)?)))
+            .collect()
+    }
+}
+
+fn gains_form(gains: Gains) -> Form<Gains> {
+    Form::new(
+        gains,
+        vec![
+            Field::new("KP", format!("{}", gains.kp), |value, state| {
+                if let Ok(value) = value.parse::<f64>() {
+                    state.kp = value;
+                    value.is_finite() && value >= 0.0
+                } else {
+                    false
+                }
+            }),
+            Field::new("KI", format!("{}", gains.ki), |value, state| {
+                if let Ok(value) = value.parse::<f64>() {
+                    state.ki = value;
+                    value.is_finite() && value >= 0.0
+                } else {
+                    false
+                }
+            }),
+            Field::new("KD", format!("{}", gains.kd), |value, state| {
+                if let Ok(value) = value.parse::<f64>() {
+                    state.kd = value;
+                    value.is_finite() && value >= 0.0
+                } else {
+                    false
+                }
+            }),
+        ],
+    )
+}
@@ -2,6 +2,9 @@ use std::sync::Mutex;
 
 const DEFAULT_XPLANE_AIRCRAFT: std::ffi::c_char = 0;
 
+// Largest dataref array we read back; scalar datarefs only use the first element
+const MAX_DATAREF_VALUES: usize = 8;
+
 mod bindings {
     #[repr(C)]
     #[cfg(target_os = "windows")]
@@ -72,6 +75,14 @@ mod bindings {
             ac: std::ffi::c_char,
         ) -> ErrorCode;
 
+        pub fn getDREFs(
+            socket: XPCSocket,
+            drefs: *const *const std::ffi::c_char,
+            values: *mut *mut std::ffi::c_float,
+            count: std::ffi::c_uchar,
+            sizes: *mut std::ffi::c_int,
+        ) -> ErrorCode;
+
         pub fn sendCTRL(
             socket: XPCSocket,
             buffer: *const [std::ffi::c_float; 7],
@@ -130,7 +141,22 @@ impl XPlaneConnection {
     }
 
     pub fn read_terrain(&self) -> Result<TerrainInfo, ()> {
-        let mut posi: [std::ffi::c_double; 3] = [-998.0; 3];
+        self.probe_terrain([-998.0; 3])
+    }
+
+    /// Terrain under an arbitrary point rather than the aircraft. Latitude and longitude are
+    /// in degrees, altitude in meters MSL.
+    pub fn read_terrain_at(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        altitude: f64,
+    ) -> Result<TerrainInfo, ()> {
+        self.probe_terrain([latitude, longitude, altitude])
+    }
+
+    // A position of -998 probes at the aircraft
+    fn probe_terrain(&self, mut posi: [std::ffi::c_double; 3]) -> Result<TerrainInfo, ()> {
         let mut buffer: [std::ffi::c_double; 11] = [0.0; 11];
         let result = unsafe {
             bindings::getTERR(self.socket, &mut posi, &mut buffer, DEFAULT_XPLANE_AIRCRAFT)
@@ -146,6 +172,45 @@ impl XPlaneConnection {
         })
     }
 
+    pub fn read_datarefs(&self, drefs: &[&str]) -> Result<Vec<Vec<f64>>, ()> {
+        let count = u8::try_from(drefs.len()).map_err(|_| ())?;
+        let names = drefs
+            .iter()
+            .map(|dref| std::ffi::CString::new(*dref).map_err(|_| ()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let name_ptrs: Vec<*const std::ffi::c_char> =
+            names.iter().map(|name| name.as_ptr()).collect();
+        let mut buffers: Vec<[std::ffi::c_float; MAX_DATAREF_VALUES]> =
+            vec![[0.0; MAX_DATAREF_VALUES]; drefs.len()];
+        let mut buffer_ptrs: Vec<*mut std::ffi::c_float> = buffers
+            .iter_mut()
+            .map(|buffer| buffer.as_mut_ptr())
+            .collect();
+        let mut sizes: Vec<std::ffi::c_int> = vec![MAX_DATAREF_VALUES as i32; drefs.len()];
+
+        let result = unsafe {
+            bindings::getDREFs(
+                self.socket,
+                name_ptrs.as_ptr(),
+                buffer_ptrs.as_mut_ptr(),
+                count,
+                sizes.as_mut_ptr(),
+            )
+        };
+
+        result.ok().map(|_| {
+            buffers
+                .iter()
+                .zip(sizes)
+                .map(|(buffer, size)| {
+                    let size = (size.max(0) as usize).min(MAX_DATAREF_VALUES);
+                    buffer[..size].iter().map(|value| *value as f64).collect()
+                })
+                .collect()
+        })
+    }
+
     pub fn write_controls(&self, controls: SetControlSurface) -> Result<(), ()> {
         let buffer = [
             controls.elevator.unwrap_or(-998.0) as f32,